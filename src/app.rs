@@ -1,11 +1,35 @@
 use crate::modules::ModuleManager;
-use clap::App;
+use clap::{App, Arg};
 
 pub fn build_app<'a, 'b>() -> (App<'a, 'b>, ModuleManager<'a, 'b>) {
 	let mut app = App::new(env!("CARGO_PKG_NAME"))
 		.version(env!("CARGO_PKG_VERSION"))
 		.author(env!("CARGO_PKG_AUTHORS"))
-		.about(env!("CARGO_PKG_DESCRIPTION"));
+		.about(env!("CARGO_PKG_DESCRIPTION"))
+		.arg(
+			Arg::with_name("SHARD_COUNTS")
+				.long("shard-counts")
+				.help("Shard count list: comma separated, 4,8 for default")
+				.takes_value(true)
+				.env("YEE_SHARD_COUNTS")
+				.global(true),
+		)
+		.arg(
+			Arg::with_name("PROFILE")
+				.long("profile")
+				.help("Profile name in config file: the default_profile for default")
+				.takes_value(true)
+				.env("YEE_PROFILE")
+				.global(true),
+		)
+		.arg(
+			Arg::with_name("CONFIG")
+				.long("config")
+				.help("Config file path: ~/.config/yee-utils/config.toml for default")
+				.takes_value(true)
+				.env("YEE_UTILS_CONFIG")
+				.global(true),
+		);
 
 	let module_manager = ModuleManager::new();
 	let subcommands = module_manager.apps();
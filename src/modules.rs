@@ -3,13 +3,18 @@ use linked_hash_map::LinkedHashMap;
 use std::iter;
 
 mod account;
+mod agent;
+mod asset;
 mod authority;
 mod base;
 mod block;
 mod completion;
+mod config;
 mod event;
 mod keystore;
+mod metadata;
 pub mod meter;
+mod runtime;
 mod state;
 mod tx;
 mod usage;
@@ -53,9 +58,12 @@ impl<'a, 'b> ModuleManager<'a, 'b> {
 		mm.register(keystore::module());
 		mm.register(meter::module());
 		mm.register(tx::module());
+		mm.register(asset::module());
 		mm.register(block::module());
 		mm.register(event::module());
 		mm.register(state::module());
+		mm.register(runtime::module());
+		mm.register(config::module());
 		mm
 	}
 
@@ -69,6 +77,11 @@ impl<'a, 'b> ModuleManager<'a, 'b> {
 	}
 
 	pub fn run(&self, name: &str, matches: &ArgMatches<'a>) {
+		if let Err(e) = init(matches) {
+			eprintln!("{}", base::output_error(e));
+			return;
+		}
+
 		let result = match name {
 			"usage" => usage::run(matches, &self.modules),
 			"completion" => completion::run(matches),
@@ -89,3 +102,13 @@ impl<'a, 'b> ModuleManager<'a, 'b> {
 		}
 	}
 }
+
+fn init(matches: &ArgMatches) -> Result<(), String> {
+	config::init(matches)?;
+
+	// the shard counts of the profile are read when needed
+	if let Some(shard_counts) = matches.value_of("SHARD_COUNTS") {
+		account::set_shard_count_list(account::parse_shard_count_list(shard_counts)?)?;
+	}
+	Ok(())
+}
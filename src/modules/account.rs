@@ -1,16 +1,44 @@
+use core::num::NonZeroU32;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use bip39::{Language, Mnemonic, MnemonicType};
 use clap::{Arg, ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use mut_static::MutStatic;
 use parity_codec::{Decode, Encode};
 use rand::thread_rng;
 use rand::Rng;
+use regex::Regex;
+use ring::digest::SHA512;
+use ring::pbkdf2;
+use schnorrkel::derive::{ChainCode, Derivation};
+use schnorrkel::{PublicKey, SecretKey};
 use serde::{Deserialize, Serialize};
+use substrate_primitives::crypto::DeriveJunction;
 use yee_primitives::{Address, AddressCodec, Hrp};
 use yee_sharding_primitives::utils;
-use yee_signer::KeyPair;
+use yee_signer::{KeyPair, Verifier, PUBLIC_KEY_LEN};
 
 use crate::modules::base::Hex;
-use crate::modules::{base, Command, Module};
+use crate::modules::keystore::{
+	put_keystore, put_keystore_with_password_list, read_password_list,
+};
+use crate::modules::{base, config, Command, Module};
+
+pub const DEFAULT_SHARD_COUNT_LIST: [u16; 2] = [4, 8];
+
+lazy_static! {
+	static ref SHARD_COUNT_LIST: MutStatic<Vec<u16>> = MutStatic::new();
+}
 
-pub const SHARD_COUNT_LIST: [u16; 2] = [4, 8];
+pub const SIGNING_CTX: &'static [u8] = b"substrate";
+
+/// Signing context of messages, different from tx so that a message signature can never be a tx signature
+pub const MESSAGE_SIGNING_CTX: &'static [u8] = b"yee-message";
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	Module {
@@ -45,18 +73,162 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.short("s")
 						.help("Shard number")
 						.takes_value(true)
-						.required(true),
+						.required_unless("DISTRIBUTE"),
 				)
 				.arg(
 					Arg::with_name("SHARD_COUNT")
 						.long("shard-count")
 						.short("c")
-						.help("Shard count")
+						.help("Shard count: the max of the shard count list for default when distribute, even across the smaller shard counts too")
 						.takes_value(true)
-						.required(true),
+						.required_unless("DISTRIBUTE"),
+				)
+				.arg(
+					Arg::with_name("COUNT")
+						.long("count")
+						.short("n")
+						.help("Account count: generate in batch and output the manifest")
+						.takes_value(true)
+						.conflicts_with("MNEMONIC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("DISTRIBUTE")
+						.long("distribute")
+						.help("Distribute the accounts evenly across shards")
+						.requires("COUNT")
+						.conflicts_with("SHARD_NUM")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEYSTORE_DIR")
+						.long("keystore-dir")
+						.short("k")
+						.help("Keystore dir: save each account to its own keystore file, secrets are not printed")
+						.takes_value(true)
+						.requires_all(&["COUNT", "TOTAL", "REQUIRE"])
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TOTAL")
+						.long("total")
+						.short("t")
+						.help("Total password keepers, shared by all keystore files")
+						.takes_value(true)
+						.requires("KEYSTORE_DIR")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("REQUIRE")
+						.long("require")
+						.short("r")
+						.help("Require password keepers")
+						.takes_value(true)
+						.requires("KEYSTORE_DIR")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MNEMONIC")
+						.long("mnemonic")
+						.short("m")
+						.help("Generate with mnemonic phrase")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("WORDS")
+						.long("words")
+						.short("w")
+						.help("Mnemonic words: 12 for default, 24")
+						.takes_value(true)
+						.possible_values(&["12", "24"])
+						.requires("MNEMONIC")
+						.required(false),
 				),
 			f: generate,
 		},
+		Command {
+			app: SubCommand::with_name("vanity")
+				.about("Generate vanity address")
+				.arg(
+					Arg::with_name("PREFIX")
+						.long("prefix")
+						.help("Prefix of the address data part: bech32 charset")
+						.takes_value(true)
+						.required_unless("SUFFIX"),
+				)
+				.arg(
+					Arg::with_name("SUFFIX")
+						.long("suffix")
+						.help("Suffix of the address data part: bech32 charset")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_NUM")
+						.long("shard-num")
+						.short("s")
+						.help("Shard number")
+						.takes_value(true)
+						.requires("SHARD_COUNT")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_COUNT")
+						.long("shard-count")
+						.short("c")
+						.help("Shard count")
+						.takes_value(true)
+						.requires("SHARD_NUM")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("THREADS")
+						.long("threads")
+						.help("Threads: 1 for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TESTNET")
+						.long("testnet")
+						.help("Match the testnet address")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEYSTORE_PATH")
+						.long("keystore-path")
+						.short("k")
+						.help("Keystore path: save the secret key to keystore file instead of printing")
+						.takes_value(true)
+						.requires_all(&["TOTAL", "REQUIRE"])
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("TOTAL")
+						.long("total")
+						.short("t")
+						.help("Total password keepers")
+						.takes_value(true)
+						.requires("KEYSTORE_PATH")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("REQUIRE")
+						.long("require")
+						.short("r")
+						.help("Require password keepers")
+						.takes_value(true)
+						.requires("KEYSTORE_PATH")
+						.required(false),
+				),
+			f: vanity,
+		},
+		Command {
+			app: SubCommand::with_name("mnemonic")
+				.about("Desc mnemonic phrase")
+				.arg(Arg::with_name("INPUT").required(false).index(1)),
+			f: mnemonic,
+		},
 		Command {
 			app: SubCommand::with_name("mini_secret_key")
 				.about("Desc mini secret key")
@@ -81,10 +253,176 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				.arg(Arg::with_name("INPUT").required(false).index(1)),
 			f: address,
 		},
+		Command {
+			app: SubCommand::with_name("shard")
+				.about("Calculate shard of account")
+				.arg(
+					Arg::with_name("COUNTS")
+						.long("counts")
+						.help("Shard counts: comma separated, the shard count list for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Public key: hex or address")
+						.required(false)
+						.index(1),
+				),
+			f: shard,
+		},
+		Command {
+			app: SubCommand::with_name("shard_map")
+				.about("Show how the shard of account changes across sharding expansions")
+				.arg(
+					Arg::with_name("COUNTS")
+						.long("counts")
+						.help("Shard counts: comma separated, the shard count list for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Public key: hex or address")
+						.required(false)
+						.index(1),
+				),
+			f: shard_map,
+		},
+		Command {
+			app: SubCommand::with_name("derive")
+				.about("Derive sub account by path")
+				.arg(
+					Arg::with_name("PATH")
+						.long("path")
+						.short("p")
+						.help("Derivation path: //hard/soft, e.g. //exchange//42")
+						.takes_value(true)
+						.required(true),
+				)
+				.args(&derive_search_args())
+				.arg(
+					Arg::with_name("HARD")
+						.long("hard")
+						.help("Search index with hard junction: soft for default to keep public derivation possible")
+						.requires("SHARD_NUM")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Mini secret key or secret key: hex")
+						.required(false)
+						.index(1),
+				),
+			f: derive,
+		},
+		Command {
+			app: SubCommand::with_name("derive_public")
+				.about("Derive watch-only sub account by soft path")
+				.arg(
+					Arg::with_name("PATH")
+						.long("path")
+						.short("p")
+						.help("Derivation path: /soft only, e.g. /exchange/42")
+						.takes_value(true)
+						.required(true),
+				)
+				.args(&derive_search_args())
+				.arg(
+					Arg::with_name("INPUT")
+						.help("Public key: hex or address")
+						.required(false)
+						.index(1),
+				),
+			f: derive_public,
+		},
+		Command {
+			app: SubCommand::with_name("sign")
+				.about("Sign message")
+				.arg(
+					Arg::with_name("SECRET_KEY")
+						.long("secret-key")
+						.short("k")
+						.help("Secret key or mini secret key: hex")
+						.takes_value(true)
+						.required(true),
+				)
+				.args(&message_args())
+				.arg(Arg::with_name("INPUT").required(false).index(1)),
+			f: sign,
+		},
+		Command {
+			app: SubCommand::with_name("verify")
+				.about("Verify message signature")
+				.arg(
+					Arg::with_name("SIGNER")
+						.long("signer")
+						.help("Signer: public key hex or address")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("SIGNATURE")
+						.long("signature")
+						.help("Signature: hex")
+						.takes_value(true)
+						.required(true),
+				)
+				.args(&message_args())
+				.arg(Arg::with_name("INPUT").required(false).index(1)),
+			f: verify,
+		},
+	]
+}
+
+fn derive_search_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+	vec![
+		Arg::with_name("SHARD_NUM")
+			.long("shard-num")
+			.short("s")
+			.help("Shard number: search the next index under the path that lands in the shard")
+			.takes_value(true)
+			.requires("SHARD_COUNT")
+			.required(false),
+		Arg::with_name("SHARD_COUNT")
+			.long("shard-count")
+			.short("c")
+			.help("Shard count")
+			.takes_value(true)
+			.requires("SHARD_NUM")
+			.required(false),
+		Arg::with_name("START")
+			.long("start")
+			.help("Start index of the search: 0 for default")
+			.takes_value(true)
+			.requires("SHARD_NUM")
+			.required(false),
+	]
+}
+
+pub fn message_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+	vec![
+		Arg::with_name("MESSAGE_FORMAT")
+			.long("message-format")
+			.short("f")
+			.help("Message format: raw for default, hex, file (input is the file path)")
+			.takes_value(true)
+			.possible_values(&["raw", "hex", "file"])
+			.required(false),
+		Arg::with_name("DOMAIN")
+			.long("domain")
+			.short("d")
+			.help("Domain: prefixed to the message to separate the usages of the signature")
+			.takes_value(true)
+			.required(false),
 	]
 }
 
 fn generate(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	if matches.is_present("COUNT") {
+		return generate_batch(matches);
+	}
+
 	let shard_num = matches
 		.value_of("SHARD_NUM")
 		.expect("qed")
@@ -96,13 +434,25 @@ fn generate(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		.parse::<u16>()
 		.map_err(|_| "Invalid shard count")?;
 
-	let (mini_secret_key, public_key, secret_key, address, testnet_address) =
-		generate_account(shard_num, shard_count)?;
+	let (mnemonic, (mini_secret_key, public_key, secret_key, address, testnet_address)) =
+		if matches.is_present("MNEMONIC") {
+			let mnemonic_type = match matches.value_of("WORDS") {
+				Some("24") => MnemonicType::Words24,
+				_ => MnemonicType::Words12,
+			};
+			let (mnemonic, account) =
+				generate_account_with_mnemonic(shard_num, shard_count, mnemonic_type)?;
+			(Some(mnemonic), account)
+		} else {
+			(None, generate_account(shard_num, shard_count)?)
+		};
 
 	#[derive(Serialize)]
 	struct Output {
 		shard_num: u16,
 		shard_count: u16,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		mnemonic: Option<String>,
 		mini_secret_key: Hex,
 		secret_key: Hex,
 		public_key: Hex,
@@ -113,6 +463,7 @@ fn generate(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let output = Output {
 		shard_num,
 		shard_count,
+		mnemonic,
 		mini_secret_key: mini_secret_key.to_vec().into(),
 		secret_key: secret_key.to_vec().into(),
 		public_key: public_key.to_vec().into(),
@@ -144,9 +495,9 @@ fn mini_secret_key(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		.to_address(Hrp::TESTNET)
 		.map_err(|_e| "Address encode failed")?;
 
-	let shard = SHARD_COUNT_LIST
-		.iter()
-		.map(|&shard_count| {
+	let shard = shard_count_list()?
+		.into_iter()
+		.map(|shard_count| {
 			let shard_num = utils::shard_num_for_bytes(&public_key, shard_count).expect("qed");
 			Shard {
 				shard_num,
@@ -177,6 +528,384 @@ fn mini_secret_key(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	base::output(&output)
 }
 
+fn generate_batch(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let count = matches
+		.value_of("COUNT")
+		.expect("qed")
+		.parse::<u32>()
+		.map_err(|_| "Invalid count")?;
+
+	let shard_count = match matches.value_of("SHARD_COUNT") {
+		Some(shard_count) => shard_count
+			.parse::<u16>()
+			.map_err(|_| "Invalid shard count")?,
+		None => shard_count_list()?
+			.into_iter()
+			.max()
+			.ok_or("Invalid shard count list")?,
+	};
+
+	let shard_num = match matches.value_of("SHARD_NUM") {
+		Some(shard_num) => {
+			let shard_num = shard_num.parse::<u16>().map_err(|_| "Invalid shard num")?;
+			if shard_num >= shard_count {
+				return Err("Invalid shard num".to_string());
+			}
+			Some(shard_num)
+		}
+		None => None,
+	};
+
+	let keystore = match matches.value_of("KEYSTORE_DIR") {
+		Some(keystore_dir) => {
+			let total = matches
+				.value_of("TOTAL")
+				.expect("qed")
+				.parse::<u8>()
+				.map_err(|_| "Invalid total")?;
+			let require = matches
+				.value_of("REQUIRE")
+				.expect("qed")
+				.parse::<u8>()
+				.map_err(|_| "Invalid require")?;
+			std::fs::create_dir_all(keystore_dir).map_err(|_| "Create keystore dir failed")?;
+			println!("Passwords shared by all keystore files: ");
+			let password_list = read_password_list(total);
+			Some((keystore_dir, password_list, require))
+		}
+		None => None,
+	};
+
+	#[derive(Serialize)]
+	struct Item {
+		index: u32,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		mini_secret_key: Option<Hex>,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		secret_key: Option<Hex>,
+		#[serde(flatten)]
+		account: DescPublicKeyOutput,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		keystore_path: Option<String>,
+	}
+
+	let accounts = (0..count)
+		.map(|index| {
+			let shard_num = shard_num.unwrap_or((index % shard_count as u32) as u16);
+			generate_account(shard_num, shard_count)
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	// check all the keystore paths before writing any, not to leave a partial batch
+	let keystore_path_list = match &keystore {
+		Some((keystore_dir, ..)) => {
+			let keystore_path_list = accounts
+				.iter()
+				.map(|(_, _, _, address, _)| {
+					Path::new(keystore_dir)
+						.join(format!("{}.json", address.0))
+						.to_string_lossy()
+						.to_string()
+				})
+				.collect::<Vec<_>>();
+			if let Some(keystore_path) = keystore_path_list
+				.iter()
+				.find(|x| std::fs::metadata(x).is_ok())
+			{
+				return Err(format!("Keystore file exists: {}", keystore_path));
+			}
+			Some(keystore_path_list)
+		}
+		None => None,
+	};
+
+	let mut items = vec![];
+	for (index, (mini_secret_key, public_key, secret_key, _address, _testnet_address)) in
+		accounts.into_iter().enumerate()
+	{
+		let index = index as u32;
+		let item = match (&keystore, &keystore_path_list) {
+			(Some((_, password_list, require)), Some(keystore_path_list)) => {
+				let keystore_path = keystore_path_list[index as usize].clone();
+				put_keystore_with_password_list(
+					&public_key,
+					&secret_key,
+					password_list.clone(),
+					*require,
+					&keystore_path,
+				)?;
+				Item {
+					index,
+					mini_secret_key: None,
+					secret_key: None,
+					account: desc_public_key(public_key.to_vec())?,
+					keystore_path: Some(keystore_path),
+				}
+			}
+			_ => Item {
+				index,
+				mini_secret_key: Some(mini_secret_key.to_vec().into()),
+				secret_key: Some(secret_key.to_vec().into()),
+				account: desc_public_key(public_key.to_vec())?,
+				keystore_path: None,
+			},
+		};
+		items.push(item);
+	}
+
+	base::output(&items)
+}
+
+fn vanity(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let prefix = matches.value_of("PREFIX").unwrap_or("").to_lowercase();
+	let suffix = matches.value_of("SUFFIX").unwrap_or("").to_lowercase();
+
+	if let Some(c) = prefix
+		.chars()
+		.chain(suffix.chars())
+		.find(|c| !BECH32_CHARSET.contains(*c))
+	{
+		return Err(format!("Invalid char for bech32: {}", c));
+	}
+
+	let shard = match (matches.value_of("SHARD_NUM"), matches.value_of("SHARD_COUNT")) {
+		(Some(shard_num), Some(shard_count)) => {
+			let shard_num = shard_num.parse::<u16>().map_err(|_| "Invalid shard num")?;
+			let shard_count = shard_count
+				.parse::<u16>()
+				.map_err(|_| "Invalid shard count")?;
+			if shard_num >= shard_count {
+				return Err("Invalid shard num".to_string());
+			}
+			Some((shard_num, shard_count))
+		}
+		_ => None,
+	};
+
+	let threads = match matches.value_of("THREADS") {
+		Some(threads) => threads.parse::<usize>().map_err(|_| "Invalid threads")?,
+		None => 1,
+	};
+	if threads == 0 {
+		return Err("Invalid threads".to_string());
+	}
+
+	let testnet = matches.is_present("TESTNET");
+
+	let keystore = match matches.value_of("KEYSTORE_PATH") {
+		Some(keystore_path) => {
+			if std::fs::File::open(keystore_path).is_ok() {
+				return Err("Keystore file exists".to_string());
+			}
+			let total = matches
+				.value_of("TOTAL")
+				.expect("qed")
+				.parse::<u8>()
+				.map_err(|_| "Invalid total")?;
+			let require = matches
+				.value_of("REQUIRE")
+				.expect("qed")
+				.parse::<u8>()
+				.map_err(|_| "Invalid require")?;
+			Some((keystore_path, total, require))
+		}
+		None => None,
+	};
+
+	let expected_attempts = {
+		let pattern_len = (prefix.len() + suffix.len()) as i32;
+		let shard_factor = shard.map(|(_, shard_count)| shard_count as f64).unwrap_or(1f64);
+		(BECH32_CHARSET.len() as f64).powi(pattern_len) * shard_factor
+	};
+	eprintln!("Expected attempts: {}", expected_attempts as u64);
+
+	let vanity_config = VanityConfig {
+		prefix,
+		suffix,
+		shard,
+		testnet,
+	};
+
+	let (mini_secret_key, attempts, elapsed) = search_vanity(vanity_config, threads)?;
+
+	let key_pair = KeyPair::from_mini_secret_key(&mini_secret_key)?;
+	let public_key = key_pair.public_key();
+	let secret_key = key_pair.secret_key();
+
+	let secret = match keystore {
+		Some((keystore_path, total, require)) => {
+			put_keystore(&public_key, &secret_key, total, require, keystore_path)?;
+			None
+		}
+		None => Some((mini_secret_key, secret_key)),
+	};
+
+	#[derive(Serialize)]
+	struct Output {
+		attempts: u64,
+		expected_attempts: u64,
+		elapsed_secs: u64,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		mini_secret_key: Option<Hex>,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		secret_key: Option<Hex>,
+		#[serde(flatten)]
+		account: DescPublicKeyOutput,
+	}
+
+	let output = Output {
+		attempts,
+		expected_attempts: expected_attempts as u64,
+		elapsed_secs: elapsed.as_secs(),
+		mini_secret_key: secret.map(|x| x.0.to_vec().into()),
+		secret_key: secret.map(|x| x.1.to_vec().into()),
+		account: desc_public_key(public_key.to_vec())?,
+	};
+
+	base::output(&output)
+}
+
+const BECH32_CHARSET: &'static str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+#[derive(Clone)]
+struct VanityConfig {
+	prefix: String,
+	suffix: String,
+	shard: Option<(u16, u16)>,
+	testnet: bool,
+}
+
+impl VanityConfig {
+	fn accept(&self, public_key: &[u8]) -> Result<bool, String> {
+		if let Some((shard_num, shard_count)) = self.shard {
+			if utils::shard_num_for_bytes(public_key, shard_count) != Some(shard_num) {
+				return Ok(false);
+			}
+		}
+		let hrp = if self.testnet {
+			Hrp::TESTNET
+		} else {
+			Hrp::MAINNET
+		};
+		let address = public_key
+			.to_address(hrp)
+			.map_err(|_e| "Address encode failed")?;
+		let data = address
+			.0
+			.rsplitn(2, '1')
+			.next()
+			.ok_or("Address encode failed")?;
+		Ok(data.starts_with(&self.prefix) && data.ends_with(&self.suffix))
+	}
+}
+
+/// Search mini secret key in threads, returns the mini secret key, total attempts and elapsed
+fn search_vanity(
+	config: VanityConfig,
+	threads: usize,
+) -> Result<([u8; 32], u64, Duration), String> {
+	let start = Instant::now();
+	let found = Arc::new(AtomicBool::new(false));
+	let attempts = Arc::new(AtomicU64::new(0));
+	let (sender, receiver) = mpsc::channel();
+
+	let handles = (0..threads)
+		.map(|_| {
+			let config = config.clone();
+			let found = found.clone();
+			let attempts = attempts.clone();
+			let sender = sender.clone();
+			thread::spawn(move || {
+				let mut rng = thread_rng();
+				while !found.load(Ordering::Relaxed) {
+					let mini_secret_key = random_32_bytes(&mut rng);
+					let result = match KeyPair::from_mini_secret_key(&mini_secret_key) {
+						Ok(key_pair) => config.accept(&key_pair.public_key()),
+						Err(_) => Err("Invalid mini secret key".to_string()),
+					};
+					attempts.fetch_add(1, Ordering::Relaxed);
+					match result {
+						Ok(true) => {
+							found.store(true, Ordering::Relaxed);
+							sender.send(Ok(mini_secret_key)).unwrap_or(());
+						}
+						Ok(false) => (),
+						Err(e) => {
+							found.store(true, Ordering::Relaxed);
+							sender.send(Err(e)).unwrap_or(());
+						}
+					}
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	drop(sender);
+
+	let result = loop {
+		match receiver.recv_timeout(VANITY_REPORT_INTERVAL) {
+			Ok(result) => break result,
+			Err(mpsc::RecvTimeoutError::Timeout) => {
+				let attempts = attempts.load(Ordering::Relaxed);
+				let elapsed = start.elapsed().as_secs_f64();
+				eprint!(
+					"\rAttempts: {}, rate: {:.0}/s",
+					attempts,
+					attempts as f64 / elapsed
+				);
+			}
+			Err(mpsc::RecvTimeoutError::Disconnected) => {
+				break Err("Search aborted".to_string());
+			}
+		}
+	};
+	eprintln!();
+
+	found.store(true, Ordering::Relaxed);
+	for handle in handles {
+		handle.join().unwrap_or(());
+	}
+
+	let mini_secret_key = result?;
+
+	Ok((
+		mini_secret_key,
+		attempts.load(Ordering::Relaxed),
+		start.elapsed(),
+	))
+}
+
+const VANITY_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+fn mnemonic(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let mini_secret_key = mini_secret_key_from_mnemonic(&input)?;
+
+	let key_pair = KeyPair::from_mini_secret_key(&mini_secret_key)?;
+
+	let secret_key = key_pair.secret_key();
+
+	let public_key = key_pair.public_key();
+
+	#[derive(Serialize)]
+	struct Output {
+		mnemonic: String,
+		mini_secret_key: Hex,
+		secret_key: Hex,
+		#[serde(flatten)]
+		account: DescPublicKeyOutput,
+	}
+
+	let output = Output {
+		mnemonic: input.trim().to_string(),
+		mini_secret_key: mini_secret_key.to_vec().into(),
+		secret_key: secret_key.to_vec().into(),
+		account: desc_public_key(public_key.to_vec())?,
+	};
+
+	base::output(&output)
+}
+
 fn secret_key(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
@@ -196,9 +925,9 @@ fn secret_key(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		.to_address(Hrp::TESTNET)
 		.map_err(|_e| "Address encode failed")?;
 
-	let shard = SHARD_COUNT_LIST
-		.iter()
-		.map(|&shard_count| {
+	let shard = shard_count_list()?
+		.into_iter()
+		.map(|shard_count| {
 			let shard_num = utils::shard_num_for_bytes(&public_key, shard_count).expect("qed");
 			Shard {
 				shard_num,
@@ -250,9 +979,9 @@ fn address(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let (public_key, hrp) =
 		<[u8; 32]>::from_address(&address).map_err(|_| "Address decode failed")?;
 
-	let shard = SHARD_COUNT_LIST
-		.iter()
-		.map(|&shard_count| {
+	let shard = shard_count_list()?
+		.into_iter()
+		.map(|shard_count| {
 			let shard_num = utils::shard_num_for_bytes(&public_key, shard_count).expect("qed");
 			Shard {
 				shard_num,
@@ -279,23 +1008,455 @@ fn address(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	base::output(&output)
 }
 
-pub fn generate_account(
-	shard_num: u16,
-	shard_count: u16,
-) -> Result<([u8; 32], [u8; 32], [u8; 64], Address, Address), String> {
-	loop {
-		let mini_secret_key = random_32_bytes(&mut thread_rng());
-		let key_pair = KeyPair::from_mini_secret_key(&mini_secret_key)?;
-		let public_key = key_pair.public_key();
-		let secret_key = key_pair.secret_key();
-		let address_shard_num = utils::shard_num_for_bytes(&public_key, shard_count);
-		if address_shard_num == Some(shard_num) {
-			let address = public_key
-				.to_address(Hrp::MAINNET)
-				.map_err(|_e| "Address encode failed")?;
-			let testnet_address = public_key
-				.to_address(Hrp::TESTNET)
-				.map_err(|_e| "Address encode failed")?;
+fn shard(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let public_key = parse_public_key(input.trim())?;
+
+	let shard_count_list = match matches.value_of("COUNTS") {
+		Some(counts) => parse_shard_count_list(counts)?,
+		None => shard_count_list()?,
+	};
+
+	let shard = get_shard_list(&public_key, &shard_count_list)?;
+
+	#[derive(Serialize)]
+	struct Output {
+		public_key: Hex,
+		shard: Vec<Shard>,
+	}
+
+	let output = Output {
+		public_key: public_key.into(),
+		shard,
+	};
+
+	base::output(&output)
+}
+
+fn shard_map(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let public_key = parse_public_key(input.trim())?;
+
+	let mut shard_count_list = match matches.value_of("COUNTS") {
+		Some(counts) => parse_shard_count_list(counts)?,
+		None => shard_count_list()?,
+	};
+	shard_count_list.sort();
+	shard_count_list.dedup();
+
+	let shard = get_shard_list(&public_key, &shard_count_list)?;
+
+	#[derive(Serialize)]
+	struct Expansion {
+		from: Shard,
+		to: Shard,
+		moved: bool,
+	}
+
+	let expansion = shard
+		.windows(2)
+		.map(|x| Expansion {
+			from: x[0].clone(),
+			to: x[1].clone(),
+			moved: x[0].shard_num != x[1].shard_num,
+		})
+		.collect::<Vec<_>>();
+
+	#[derive(Serialize)]
+	struct Output {
+		public_key: Hex,
+		shard: Vec<Shard>,
+		expansion: Vec<Expansion>,
+	}
+
+	let output = Output {
+		public_key: public_key.into(),
+		shard,
+		expansion,
+	};
+
+	base::output(&output)
+}
+
+/// Parse public key from hex or address
+pub fn parse_public_key(input: &str) -> Result<Vec<u8>, String> {
+	let public_key: Vec<u8> = match input.parse::<Hex>() {
+		Ok(public_key) => public_key.into(),
+		Err(_) => {
+			let address = Address(input.to_string());
+			let (public_key, _hrp) =
+				<[u8; 32]>::from_address(&address).map_err(|_| "Address decode failed")?;
+			public_key.to_vec()
+		}
+	};
+	if public_key.len() != PUBLIC_KEY_LEN {
+		return Err("Invalid public key length".to_string());
+	}
+	Ok(public_key)
+}
+
+pub fn get_shard_list(public_key: &[u8], shard_count_list: &[u16]) -> Result<Vec<Shard>, String> {
+	shard_count_list
+		.iter()
+		.map(|&shard_count| {
+			let shard_num = utils::shard_num_for_bytes(public_key, shard_count)
+				.ok_or("Invalid shard count")?;
+			Ok(Shard {
+				shard_num,
+				shard_count,
+			})
+		})
+		.collect()
+}
+
+/// --shard-counts, or the shard counts of the profile, or the default
+pub fn shard_count_list() -> Result<Vec<u16>, String> {
+	if let Ok(shard_count_list) = SHARD_COUNT_LIST.read() {
+		return Ok(shard_count_list.to_vec());
+	}
+	let shard_count_list = config::active_profile()?
+		.and_then(|x| x.profile.shard_counts)
+		.unwrap_or_else(|| DEFAULT_SHARD_COUNT_LIST.to_vec());
+	Ok(shard_count_list)
+}
+
+pub fn set_shard_count_list(shard_count_list: Vec<u16>) -> Result<(), String> {
+	SHARD_COUNT_LIST
+		.set(shard_count_list)
+		.map_err(|_| "Failed to set shard count list")?;
+	Ok(())
+}
+
+pub fn parse_shard_count_list(input: &str) -> Result<Vec<u16>, String> {
+	input
+		.split(',')
+		.map(|x| {
+			let shard_count = x.trim().parse::<u16>().map_err(|_| "Invalid shard count")?;
+			if !shard_count.is_power_of_two() {
+				return Err("Invalid shard count: should be power of 2".to_string());
+			}
+			Ok(shard_count)
+		})
+		.collect()
+}
+
+fn derive(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input
+		.trim()
+		.parse::<Hex>()
+		.map_err(|_| "Invalid secret key")?
+		.into();
+
+	let secret_key = match input.len() {
+		32 => KeyPair::from_mini_secret_key(&input)?.secret_key().to_vec(),
+		64 => input,
+		_ => return Err("Invalid secret key length".to_string()),
+	};
+
+	let path = matches.value_of("PATH").expect("qed");
+	let junctions = parse_derive_path(path)?;
+
+	let hard = matches.is_present("HARD");
+	let index_junction = if hard { "//" } else { "/" };
+
+	let search = get_derive_search(matches)?;
+
+	let (index, secret_key) = match search {
+		Some((shard_num, shard_count, start)) => {
+			let mut index = start;
+			loop {
+				let mut junctions = junctions.clone();
+				junctions.push(if hard {
+					DeriveJunction::hard(index)
+				} else {
+					DeriveJunction::soft(index)
+				});
+				let secret_key = derive_secret_key(&secret_key, &junctions)?;
+				let public_key = KeyPair::from_secret_key(&secret_key)?.public_key();
+				if utils::shard_num_for_bytes(&public_key, shard_count) == Some(shard_num) {
+					break (Some(index), secret_key);
+				}
+				index = index.checked_add(1).ok_or("Index overflow")?;
+			}
+		}
+		None => (None, derive_secret_key(&secret_key, &junctions)?),
+	};
+
+	let path = match index {
+		Some(index) => format!("{}{}{}", path, index_junction, index),
+		None => path.to_string(),
+	};
+
+	let public_key = KeyPair::from_secret_key(&secret_key)?.public_key();
+
+	#[derive(Serialize)]
+	struct Output {
+		path: String,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		index: Option<u64>,
+		secret_key: Hex,
+		#[serde(flatten)]
+		account: DescPublicKeyOutput,
+	}
+
+	let output = Output {
+		path,
+		index,
+		secret_key: secret_key.to_vec().into(),
+		account: desc_public_key(public_key.to_vec())?,
+	};
+
+	base::output(&output)
+}
+
+fn derive_public(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+	let input = input.trim();
+
+	let public_key = parse_public_key(input)?;
+
+	let path = matches.value_of("PATH").expect("qed");
+	let junctions = parse_derive_path(path)?;
+
+	let search = get_derive_search(matches)?;
+
+	let (index, public_key) = match search {
+		Some((shard_num, shard_count, start)) => {
+			let mut index = start;
+			loop {
+				let mut junctions = junctions.clone();
+				junctions.push(DeriveJunction::soft(index));
+				let public_key = derive_public_key(&public_key, &junctions)?;
+				if utils::shard_num_for_bytes(&public_key, shard_count) == Some(shard_num) {
+					break (Some(index), public_key);
+				}
+				index = index.checked_add(1).ok_or("Index overflow")?;
+			}
+		}
+		None => (None, derive_public_key(&public_key, &junctions)?),
+	};
+
+	let path = match index {
+		Some(index) => format!("{}/{}", path, index),
+		None => path.to_string(),
+	};
+
+	#[derive(Serialize)]
+	struct Output {
+		path: String,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		index: Option<u64>,
+		#[serde(flatten)]
+		account: DescPublicKeyOutput,
+	}
+
+	let output = Output {
+		path,
+		index,
+		account: desc_public_key(public_key.to_vec())?,
+	};
+
+	base::output(&output)
+}
+
+/// Shard num, shard count and start index to search the derived account in a shard
+fn get_derive_search(matches: &ArgMatches) -> Result<Option<(u16, u16, u64)>, String> {
+	let shard_num = match matches.value_of("SHARD_NUM") {
+		Some(shard_num) => shard_num.parse::<u16>().map_err(|_| "Invalid shard num")?,
+		None => return Ok(None),
+	};
+	let shard_count = matches
+		.value_of("SHARD_COUNT")
+		.expect("qed")
+		.parse::<u16>()
+		.map_err(|_| "Invalid shard count")?;
+	if shard_num >= shard_count {
+		return Err("Invalid shard num".to_string());
+	}
+	let start = match matches.value_of("START") {
+		Some(start) => start.parse::<u64>().map_err(|_| "Invalid start")?,
+		None => 0,
+	};
+	Ok(Some((shard_num, shard_count, start)))
+}
+
+pub fn parse_derive_path(path: &str) -> Result<Vec<DeriveJunction>, String> {
+	let path_re = Regex::new(r"^(//?[^/]+)*$").expect("qed");
+	if !path_re.is_match(path) {
+		return Err("Invalid derivation path".to_string());
+	}
+	let junction_re = Regex::new(r"/(/?[^/]+)").expect("qed");
+	let junctions = junction_re
+		.captures_iter(path)
+		.map(|x| DeriveJunction::from(&x[1]))
+		.collect::<Vec<_>>();
+	Ok(junctions)
+}
+
+pub fn derive_secret_key(secret_key: &[u8], junctions: &[DeriveJunction]) -> Result<Vec<u8>, String> {
+	let secret_key = SecretKey::from_bytes(secret_key).map_err(|_| "Invalid secret key")?;
+	let key_pair = junctions
+		.iter()
+		.fold(secret_key.to_keypair(), |key_pair, junction| match junction {
+			DeriveJunction::Soft(cc) => key_pair.derived_key_simple(ChainCode(*cc), &[]).0,
+			DeriveJunction::Hard(cc) => key_pair
+				.secret
+				.hard_derive_mini_secret_key(Some(ChainCode(*cc)), b"")
+				.0
+				.expand_to_keypair(),
+		});
+	Ok(key_pair.secret.to_bytes().to_vec())
+}
+
+pub fn derive_public_key(public_key: &[u8], junctions: &[DeriveJunction]) -> Result<Vec<u8>, String> {
+	let public_key = PublicKey::from_bytes(public_key).map_err(|_| "Invalid public key")?;
+	let public_key = junctions
+		.iter()
+		.try_fold(public_key, |public_key, junction| match junction {
+			DeriveJunction::Soft(cc) => Ok(public_key.derived_key_simple(ChainCode(*cc), &[]).0),
+			DeriveJunction::Hard(_) => Err("Hard junction can not be derived from public key"),
+		})?;
+	Ok(public_key.to_bytes().to_vec())
+}
+
+fn sign(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let secret_key = matches.value_of("SECRET_KEY").expect("qed");
+	let secret_key: Vec<u8> = secret_key
+		.parse::<Hex>()
+		.map_err(|_| "Invalid secret key")?
+		.into();
+
+	let key_pair = match secret_key.len() {
+		32 => KeyPair::from_mini_secret_key(&secret_key)?,
+		64 => KeyPair::from_secret_key(&secret_key)?,
+		_ => return Err("Invalid secret key length".to_string()),
+	};
+
+	let message = get_message(matches)?;
+
+	let output = sign_message(&key_pair, message)?;
+
+	base::output(output)
+}
+
+fn verify(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let signer = matches.value_of("SIGNER").expect("qed");
+
+	let public_key = parse_public_key(signer)?;
+
+	let signature: Vec<u8> = matches
+		.value_of("SIGNATURE")
+		.expect("qed")
+		.parse::<Hex>()
+		.map_err(|_| "Invalid signature")?
+		.into();
+
+	let message = get_message(matches)?;
+
+	let valid = verify_message_signature(&public_key, &message, &signature)?;
+
+	#[derive(Serialize)]
+	struct Output {
+		valid: bool,
+		message: Hex,
+		signature: Hex,
+		#[serde(flatten)]
+		signer: DescPublicKeyOutput,
+	}
+
+	let output = Output {
+		valid,
+		message: message.into(),
+		signature: signature.into(),
+		signer: desc_public_key(public_key)?,
+	};
+
+	base::output(output)
+}
+
+/// Get the message to be signed or verified, with the domain prefixed
+pub fn get_message(matches: &ArgMatches) -> Result<Vec<u8>, String> {
+	let message = match matches.value_of("MESSAGE_FORMAT") {
+		Some("hex") => base::input_string(matches)?
+			.trim()
+			.parse::<Hex>()
+			.map_err(|_| "Invalid message")?
+			.into(),
+		Some("file") => base::get_from_file(&base::input_string(matches)?)?,
+		_ => base::input_bytes(matches)?,
+	};
+
+	let message = match matches.value_of("DOMAIN") {
+		Some(domain) => {
+			let mut tmp = domain.as_bytes().to_vec();
+			tmp.extend(message);
+			tmp
+		}
+		None => message,
+	};
+
+	Ok(message)
+}
+
+#[derive(Serialize)]
+pub struct SignMessageOutput {
+	message: Hex,
+	signature: Hex,
+	#[serde(flatten)]
+	signer: DescPublicKeyOutput,
+}
+
+pub fn sign_message(key_pair: &KeyPair, message: Vec<u8>) -> Result<SignMessageOutput, String> {
+	let signature = key_pair.sign(&message, MESSAGE_SIGNING_CTX);
+
+	let output = SignMessageOutput {
+		message: message.into(),
+		signature: signature.to_vec().into(),
+		signer: desc_public_key(key_pair.public_key().to_vec())?,
+	};
+
+	Ok(output)
+}
+
+/// Verify the signature of tx payload
+pub fn verify_signature(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, String> {
+	let verifier = Verifier::from_public_key(public_key)?;
+
+	Ok(verifier.verify(signature, message, SIGNING_CTX).is_ok())
+}
+
+/// Verify the signature of message, signed by sign_message
+pub fn verify_message_signature(
+	public_key: &[u8],
+	message: &[u8],
+	signature: &[u8],
+) -> Result<bool, String> {
+	let verifier = Verifier::from_public_key(public_key)?;
+
+	Ok(verifier.verify(signature, message, MESSAGE_SIGNING_CTX).is_ok())
+}
+
+pub fn generate_account(
+	shard_num: u16,
+	shard_count: u16,
+) -> Result<([u8; 32], [u8; 32], [u8; 64], Address, Address), String> {
+	loop {
+		let mini_secret_key = random_32_bytes(&mut thread_rng());
+		let key_pair = KeyPair::from_mini_secret_key(&mini_secret_key)?;
+		let public_key = key_pair.public_key();
+		let secret_key = key_pair.secret_key();
+		let address_shard_num = utils::shard_num_for_bytes(&public_key, shard_count);
+		if address_shard_num == Some(shard_num) {
+			let address = public_key
+				.to_address(Hrp::MAINNET)
+				.map_err(|_e| "Address encode failed")?;
+			let testnet_address = public_key
+				.to_address(Hrp::TESTNET)
+				.map_err(|_e| "Address encode failed")?;
 
 			break Ok((
 				mini_secret_key,
@@ -308,6 +1469,60 @@ pub fn generate_account(
 	}
 }
 
+pub fn generate_account_with_mnemonic(
+	shard_num: u16,
+	shard_count: u16,
+	mnemonic_type: MnemonicType,
+) -> Result<(String, ([u8; 32], [u8; 32], [u8; 64], Address, Address)), String> {
+	loop {
+		let mnemonic = Mnemonic::new(mnemonic_type, Language::English);
+		let mini_secret_key = mini_secret_key_from_entropy(mnemonic.entropy());
+		let key_pair = KeyPair::from_mini_secret_key(&mini_secret_key)?;
+		let public_key = key_pair.public_key();
+		let secret_key = key_pair.secret_key();
+		let address_shard_num = utils::shard_num_for_bytes(&public_key, shard_count);
+		if address_shard_num == Some(shard_num) {
+			let address = public_key
+				.to_address(Hrp::MAINNET)
+				.map_err(|_e| "Address encode failed")?;
+			let testnet_address = public_key
+				.to_address(Hrp::TESTNET)
+				.map_err(|_e| "Address encode failed")?;
+
+			break Ok((
+				mnemonic.phrase().to_string(),
+				(
+					mini_secret_key,
+					public_key,
+					secret_key,
+					address,
+					testnet_address,
+				),
+			));
+		}
+	}
+}
+
+pub fn mini_secret_key_from_mnemonic(phrase: &str) -> Result<[u8; 32], String> {
+	let mnemonic =
+		Mnemonic::from_phrase(phrase.trim(), Language::English).map_err(|_| "Invalid mnemonic")?;
+	Ok(mini_secret_key_from_entropy(mnemonic.entropy()))
+}
+
+const MNEMONIC_ITERATIONS: u32 = 2048;
+const MNEMONIC_SALT: &'static [u8] = b"mnemonic";
+
+/// The same as substrate-bip39: the seed is derived from the entropy rather than the phrase
+fn mini_secret_key_from_entropy(entropy: &[u8]) -> [u8; 32] {
+	let iterations = NonZeroU32::new(MNEMONIC_ITERATIONS).expect("qed");
+	let mut seed = [0u8; 64];
+	pbkdf2::derive(&SHA512, iterations, MNEMONIC_SALT, entropy, &mut seed);
+
+	let mut mini_secret_key = [0u8; 32];
+	mini_secret_key.copy_from_slice(&seed[0..32]);
+	mini_secret_key
+}
+
 #[derive(Encode, Serialize, Decode, Deserialize, Clone, Debug)]
 pub struct Shard {
 	pub shard_num: u16,
@@ -330,9 +1545,9 @@ pub fn desc_public_key(public_key: Vec<u8>) -> Result<DescPublicKeyOutput, Strin
 		.to_address(Hrp::TESTNET)
 		.map_err(|_e| "Address encode failed")?;
 
-	let shard = SHARD_COUNT_LIST
-		.iter()
-		.map(|&shard_count| {
+	let shard = shard_count_list()?
+		.into_iter()
+		.map(|shard_count| {
 			let shard_num = utils::shard_num_for_bytes(&public_key, shard_count).expect("qed");
 			Shard {
 				shard_num,
@@ -384,6 +1599,122 @@ mod cases {
 					is_example: true,
 					is_test: false,
 					since: "0.1.0".to_string(),
+				}, Case {
+					desc: "Generate accounts in batch distributed across shards".to_string(),
+					input: vec!["generate", "-n", "2", "--distribute", "-c", "4", "-k", "./keystores", "-t", "1", "-r", "1"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": [
+    {
+      "index": 0,
+      "public_key": "0xe28347295d52831538c1ebb347ddd873aadb509ca2ef7b23c97645dad2ea5e10",
+      "address": "yee1u2p5w22a22p32wxpawe50hwcww4dk5yu5thhkg7fweza45h2tcgqaxn6g6",
+      "testnet_address": "tyee1u2p5w22a22p32wxpawe50hwcww4dk5yu5thhkg7fweza45h2tcgqsp5vff",
+      "shard": [
+        {
+          "shard_num": 0,
+          "shard_count": 4
+        },
+        {
+          "shard_num": 0,
+          "shard_count": 8
+        }
+      ],
+      "keystore_path": "./keystores/yee1u2p5w22a22p32wxpawe50hwcww4dk5yu5thhkg7fweza45h2tcgqaxn6g6.json"
+    },
+    {
+      "index": 1,
+      "public_key": "0x4a823c8d656a97148d21322727c156c94b38d6f9d759f15e7bdbd98ab4c1d565",
+      "address": "yee1f2prert9d2t3frfpxgnj0s2ke99n34he6avlzhnmm0vc4dxp64js755ykt",
+      "testnet_address": "tyee1f2prert9d2t3frfpxgnj0s2ke99n34he6avlzhnmm0vc4dxp64jsnnnjhc",
+      "shard": [
+        {
+          "shard_num": 1,
+          "shard_count": 4
+        },
+        {
+          "shard_num": 5,
+          "shard_count": 8
+        }
+      ],
+      "keystore_path": "./keystores/yee1f2prert9d2t3frfpxgnj0s2ke99n34he6avlzhnmm0vc4dxp64js755ykt.json"
+    }
+  ]
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Generate account with mnemonic".to_string(),
+					input: vec!["generate", "-s", "0", "-c", "4", "--mnemonic"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "shard_num": 0,
+    "shard_count": 4,
+    "mnemonic": "solve iron desert claim armor vibrant wool across bottom result deal normal",
+    "mini_secret_key": "0xa913b25fd2540485bd415ad2759e9ac35b3dcecce09d0e4931b8f4b26ce95305",
+    "secret_key": "0x28d5f0bd943be351c5642438ec5754b8dd1a9bf582ee928d6125bfed690ebb677193ca52b029b0096e438a556a4f018dba7bb81e1cdc1bb5fb393e9cc428264f",
+    "public_key": "0xa8dc35b42ea6c39b8c51972182a0119aea82a4ef864417025ca5efd2cbc43960",
+    "address": "yee14rwrtdpw5mpehrz3jusc9gq3nt4g9f80sezpwqju5hha9j7y89sqy2ngcp",
+    "testnet_address": "tyee14rwrtdpw5mpehrz3jusc9gq3nt4g9f80sezpwqju5hha9j7y89sqfd57ej"
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Generate vanity address".to_string(),
+					input: vec!["vanity", "--prefix", "yee", "-s", "2", "-c", "4", "--threads", "4"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "attempts": 82755,
+    "expected_attempts": 131072,
+    "elapsed_secs": 3,
+    "mini_secret_key": "0x6b74d2ce5e416721edc02c619d55a6be54931a881cfd2fff95f6ccd785b41fcc",
+    "secret_key": "0x6089629d4b753c022e74378d89784ad12c2dcff3c7e9da93bd2c5da5f6518a5da2cb2b7d8ad6f13f7807d01d7f990e26bd0d3f7595b54a24b3a6b657729e5ff1",
+    "public_key": "0x26733feeaa3379a71511360e24572bc5858d9897db9309583686bba88e91ac4e",
+    "address": "yee1yeenlm42xdu6w9g3xc8zg4etckzcmxyhmwfsjkpks6a63r53438qruf8dq",
+    "testnet_address": "tyee1yeenlm42xdu6w9g3xc8zg4etckzcmxyhmwfsjkpks6a63r53438qwmw3vn",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 6,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Desc mnemonic phrase".to_string(),
+					input: vec!["mnemonic", "'bottom drive obey lake curtain smoke basket hold race lonely fit walk'"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "mnemonic": "bottom drive obey lake curtain smoke basket hold race lonely fit walk",
+    "mini_secret_key": "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e",
+    "secret_key": "0x28b0ae221c6bb06856b287f60d7ea0d98552ea5a16db16956849aa371db3eb51fd190cce74df356432b410bd64682309d6dedb27c76845daf388557cbac3ca34",
+    "public_key": "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+    "address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "testnet_address": "tyee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aqzckf7l",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
 				}, Case {
 					desc: "Desc mini secret key".to_string(),
 					input: vec!["mini_secret_key", "0xbd08b0bf13e4489e167e34b38189813098f6ce58ca35cb562d2bdec19ddbe08d"].into_iter().map(Into::into).collect(),
@@ -479,6 +1810,312 @@ mod cases {
 					is_example: true,
 					is_test: true,
 					since: "0.1.0".to_string(),
+				}, Case {
+					desc: "Calculate shard of account".to_string(),
+					input: vec!["shard", "--counts", "2,4,8,16", "yee1wmffva8zfwfvm4d57t7etp4lycml4xgcg2f2v97qk4ensw7r8szqd0acf7"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "public_key": "0x76d29674e24b92cdd5b4f2fd9586bf2637fa99184292a617c0b573383bc33c04",
+    "shard": [
+      {
+        "shard_num": 0,
+        "shard_count": 2
+      },
+      {
+        "shard_num": 0,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 4,
+        "shard_count": 8
+      },
+      {
+        "shard_num": 4,
+        "shard_count": 16
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Show shard map of account".to_string(),
+					input: vec!["shard_map", "--counts", "4,8,16", "0x76d29674e24b92cdd5b4f2fd9586bf2637fa99184292a617c0b573383bc33c04"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "public_key": "0x76d29674e24b92cdd5b4f2fd9586bf2637fa99184292a617c0b573383bc33c04",
+    "shard": [
+      {
+        "shard_num": 0,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 4,
+        "shard_count": 8
+      },
+      {
+        "shard_num": 4,
+        "shard_count": 16
+      }
+    ],
+    "expansion": [
+      {
+        "from": {
+          "shard_num": 0,
+          "shard_count": 4
+        },
+        "to": {
+          "shard_num": 4,
+          "shard_count": 8
+        },
+        "moved": true
+      },
+      {
+        "from": {
+          "shard_num": 4,
+          "shard_count": 8
+        },
+        "to": {
+          "shard_num": 4,
+          "shard_count": 16
+        },
+        "moved": false
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Derive sub account".to_string(),
+					input: vec!["derive", "-p", "//Alice", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "path": "//Alice",
+    "secret_key": "0x98319d4ff8a9508c4bb0cf0b5a78d760a0b2082c02775e6e82370816fedfff48925a225d97aa00682d6a59b95b18780c10d7032336e88f3442b42361f4a66011",
+    "public_key": "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+    "address": "yee16s6e83c4lhf3ccg5r27sf2vl66pzep2cs4xvmcu626zw0ftd5f7sa3d3h7",
+    "testnet_address": "tyee16s6e83c4lhf3ccg5r27sf2vl66pzep2cs4xvmcu626zw0ftd5f7ssk28kd",
+    "shard": [
+      {
+        "shard_num": 1,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 5,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Derive sub account in shard".to_string(),
+					input: vec!["derive", "-p", "//Alice", "-s", "2", "-c", "4", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "path": "//Alice/4",
+    "index": 4,
+    "secret_key": "0xd03dba026d820990122923e54e3fcbf3755eba6945dca77af6eb2b88d4cadc5855922d5bc759d9e7d07956c896f1923f772d31bbb043b2cd9642f44dbe5e9f04",
+    "public_key": "0x2809b762e38460caa36a901d475598a829d6a101882a39cb1f2eb1ad548c4562",
+    "address": "yee19qymwchrs3sv4gm2jqw5w4vc4q5adggp3q4rnjcl96c664yvg43qfdummq",
+    "testnet_address": "tyee19qymwchrs3sv4gm2jqw5w4vc4q5adggp3q4rnjcl96c664yvg43qy2md6n",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Derive sub account by soft junction".to_string(),
+					input: vec!["derive", "-p", "//Alice/soft", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "path": "//Alice/soft",
+    "secret_key": "0xd07386da20444a0779e908e44be5a013782cd2a2e8186c7e3df07c53cb32080dec17c0a8e0a222ffcc2512460b0069e8f5e8add1294bb7ed8260a07f5721c969",
+    "public_key": "0x02cfd83074aefc9955af4034d19b3780d47a52e158ababec8ec012b2295f1c5b",
+    "address": "yee1qt8asvr54m7fj4d0gq6drxehsr2855hptz46hmywcqfty22lr3dswu3rk3",
+    "testnet_address": "tyee1qt8asvr54m7fj4d0gq6drxehsr2855hptz46hmywcqfty22lr3dsrmk4hz",
+    "shard": [
+      {
+        "shard_num": 3,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 3,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Derive sub account in shard by hard junction".to_string(),
+					input: vec!["derive", "-p", "//Alice", "-s", "2", "-c", "4", "--hard", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "path": "//Alice//1",
+    "index": 1,
+    "secret_key": "0xc0357e4b76de38fd39b503a8c02ad24600331279c38d53e920d42a9413d8394312ab372d58537dd3e23a5ad6f684ee804fe18b3cad90bcafbfc7afc4f60a52b1",
+    "public_key": "0x70cd06e4c5d36fd8fe158e7b5e102937d4859d45933f39074ae784a67bf65426",
+    "address": "yee1wrxsdex96dha3ls43ea4uypfxl2gt829jvlnjp62u7z2v7lk2snqhpejuv",
+    "testnet_address": "tyee1wrxsdex96dha3ls43ea4uypfxl2gt829jvlnjp62u7z2v7lk2snq6x7yal",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 6,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Derive watch-only sub account".to_string(),
+					input: vec!["derive_public", "-p", "/soft", "yee16s6e83c4lhf3ccg5r27sf2vl66pzep2cs4xvmcu626zw0ftd5f7sa3d3h7"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "path": "/soft",
+    "public_key": "0x02cfd83074aefc9955af4034d19b3780d47a52e158ababec8ec012b2295f1c5b",
+    "address": "yee1qt8asvr54m7fj4d0gq6drxehsr2855hptz46hmywcqfty22lr3dswu3rk3",
+    "testnet_address": "tyee1qt8asvr54m7fj4d0gq6drxehsr2855hptz46hmywcqfty22lr3dsrmk4hz",
+    "shard": [
+      {
+        "shard_num": 3,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 3,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Derive watch-only sub account in shard".to_string(),
+					input: vec!["derive_public", "-p", "/soft", "-s", "2", "-c", "4", "yee16s6e83c4lhf3ccg5r27sf2vl66pzep2cs4xvmcu626zw0ftd5f7sa3d3h7"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "path": "/soft/3",
+    "index": 3,
+    "public_key": "0x00539801b368be7870adbaf272f80dc80ddd75f429f1cdfecc6a3b4183cfba62",
+    "address": "yee1qpfesqdndzl8su9dhte897qdeqxa6a0598cumlkvdga5rq70hf3qax5wfe",
+    "testnet_address": "tyee1qpfesqdndzl8su9dhte897qdeqxa6a0598cumlkvdga5rq70hf3qspncg2",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Sign message".to_string(),
+					input: vec!["sign", "-k", "0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e", "-d", "yee-deposit:", "hello"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "message": "0x7965652d6465706f7369743a68656c6c6f",
+    "signature": "0xaa2b4a116e35d7b715e52b8bcf3691c9b666ca4f11f32c8bfa2552749e5e6f465846d76954f3f925de3bb6fe9c287809a78fadb3df973a12a991278ca4330706",
+    "public_key": "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+    "address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "testnet_address": "tyee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aqzckf7l",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Verify message signature".to_string(),
+					input: vec!["verify", "--signer", "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv", "--signature", "0xaa2b4a116e35d7b715e52b8bcf3691c9b666ca4f11f32c8bfa2552749e5e6f465846d76954f3f925de3bb6fe9c287809a78fadb3df973a12a991278ca4330706", "-d", "yee-deposit:", "hello"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "valid": true,
+    "message": "0x7965652d6465706f7369743a68656c6c6f",
+    "signature": "0xaa2b4a116e35d7b715e52b8bcf3691c9b666ca4f11f32c8bfa2552749e5e6f465846d76954f3f925de3bb6fe9c287809a78fadb3df973a12a991278ca4330706",
+    "public_key": "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+    "address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "testnet_address": "tyee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aqzckf7l",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				}, Case {
+					desc: "Verify message signature of a changed message".to_string(),
+					input: vec!["verify", "--signer", "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv", "--signature", "0xaa2b4a116e35d7b715e52b8bcf3691c9b666ca4f11f32c8bfa2552749e5e6f465846d76954f3f925de3bb6fe9c287809a78fadb3df973a12a991278ca4330706", "-d", "yee-deposit:", "hellp"].into_iter().map(Into::into).collect(),
+					output: vec![r#"{
+  "result": {
+    "valid": false,
+    "message": "0x7965652d6465706f7369743a68656c6c70",
+    "signature": "0xaa2b4a116e35d7b715e52b8bcf3691c9b666ca4f11f32c8bfa2552749e5e6f465846d76954f3f925de3bb6fe9c287809a78fadb3df973a12a991278ca4330706",
+    "public_key": "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+    "address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "testnet_address": "tyee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aqzckf7l",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+					is_example: false,
+					is_test: true,
+					since: "0.14.0".to_string(),
 				}, ],
 			),
 		].into_iter().collect()
@@ -495,4 +2132,23 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_derive_soft_search() {
+		// the nonce half of a soft derived secret key is random, so check its public key only
+		let secret_key: Vec<u8> = "0x28b0ae221c6bb06856b287f60d7ea0d98552ea5a16db16956849aa371db3eb51fd190cce74df356432b410bd64682309d6dedb27c76845daf388557cbac3ca34"
+			.parse::<Hex>()
+			.unwrap()
+			.into();
+		let shard_nums = (0..5u64)
+			.map(|index| {
+				let mut junctions = parse_derive_path("//Alice").unwrap();
+				junctions.push(DeriveJunction::soft(index));
+				let secret_key = derive_secret_key(&secret_key, &junctions).unwrap();
+				let public_key = KeyPair::from_secret_key(&secret_key).unwrap().public_key();
+				utils::shard_num_for_bytes(&public_key, 4).unwrap()
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(shard_nums, vec![1, 1, 1, 1, 2]);
+	}
 }
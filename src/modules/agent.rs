@@ -0,0 +1,449 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use yee_signer::KeyPair;
+use zeroize::Zeroizing;
+
+use crate::modules::account::SIGNING_CTX;
+use crate::modules::base::Hex;
+
+pub struct AgentConfig {
+	pub socket_path: String,
+	pub timeout: Option<Duration>,
+	pub confirm: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+enum AgentRequest {
+	List,
+	Sign { public_key: Hex, payload: Hex },
+	Lock,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AgentResponse<T> {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	result: Option<T>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+}
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A client must send each request within the time, or it is disconnected
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serve the unlocked secret keys on a unix socket until locked, idle timeout or interrupted
+pub fn serve(secret_key_list: Vec<Zeroizing<Vec<u8>>>, config: AgentConfig) -> Result<(), String> {
+	if std::fs::metadata(&config.socket_path).is_ok() {
+		return Err("Socket file exists".to_string());
+	}
+
+	// the key pairs are built once, not to leave a copy of the secret key per request
+	let key_pair_list = secret_key_list
+		.iter()
+		.map(|secret_key| KeyPair::from_secret_key(secret_key))
+		.collect::<Result<Vec<_>, _>>()?;
+	drop(secret_key_list);
+
+	let listener = bind(&config.socket_path)?;
+	listener
+		.set_nonblocking(true)
+		.map_err(|_| "Set nonblocking failed")?;
+
+	let interrupted = Arc::new(AtomicBool::new(false));
+	{
+		let interrupted = interrupted.clone();
+		ctrlc::set_handler(move || interrupted.store(true, Ordering::SeqCst))
+			.map_err(|_| "Set interrupt handler failed")?;
+	}
+
+	let result = accept_loop(&listener, &key_pair_list, &config, &interrupted);
+
+	std::fs::remove_file(&config.socket_path).unwrap_or(());
+
+	result
+}
+
+/// Bind the socket in a private dir and move it to the path once only the owner can access it,
+/// so no one can connect in between
+fn bind(socket_path: &str) -> Result<UnixListener, String> {
+	let socket_path = Path::new(socket_path);
+	let file_name = socket_path.file_name().ok_or("Invalid socket path")?;
+	let private_dir = socket_path.with_file_name(format!(
+		".{}.{}",
+		file_name.to_string_lossy(),
+		std::process::id()
+	));
+	std::fs::DirBuilder::new()
+		.mode(0o700)
+		.create(&private_dir)
+		.map_err(|e| format!("Create socket dir failed: {:?}", e))?;
+
+	let private_path = private_dir.join(file_name);
+	let result = UnixListener::bind(&private_path)
+		.map_err(|e| format!("Bind failed: {:?}", e))
+		.and_then(|listener| {
+			std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(0o600))
+				.map_err(|_| "Set socket permissions failed")?;
+			std::fs::rename(&private_path, socket_path).map_err(|_| "Move socket failed")?;
+			Ok(listener)
+		});
+
+	std::fs::remove_file(&private_path).unwrap_or(());
+	std::fs::remove_dir(&private_dir).unwrap_or(());
+
+	result
+}
+
+fn accept_loop(
+	listener: &UnixListener,
+	key_pair_list: &[KeyPair],
+	config: &AgentConfig,
+	interrupted: &AtomicBool,
+) -> Result<(), String> {
+	let mut last_active = Instant::now();
+	loop {
+		if should_stop(config, interrupted, last_active) {
+			return Ok(());
+		}
+		match listener.accept() {
+			Ok((stream, _)) => {
+				let stop =
+					handle_stream(stream, key_pair_list, config, interrupted, &mut last_active)
+						.unwrap_or(false);
+				if stop {
+					return Ok(());
+				}
+			}
+			Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+				std::thread::sleep(POLL_INTERVAL);
+			}
+			Err(e) => return Err(format!("Accept failed: {:?}", e)),
+		}
+	}
+}
+
+/// Interrupted, or idle until the lock deadline
+fn should_stop(config: &AgentConfig, interrupted: &AtomicBool, last_active: Instant) -> bool {
+	if interrupted.load(Ordering::SeqCst) {
+		return true;
+	}
+	match lock_deadline(config, last_active) {
+		Some(deadline) => Instant::now() >= deadline,
+		None => false,
+	}
+}
+
+fn lock_deadline(config: &AgentConfig, last_active: Instant) -> Option<Instant> {
+	config.timeout.map(|timeout| last_active + timeout)
+}
+
+/// Returns true if the agent should stop: requested to lock, interrupted or the lock deadline passed
+fn handle_stream(
+	stream: UnixStream,
+	key_pair_list: &[KeyPair],
+	config: &AgentConfig,
+	interrupted: &AtomicBool,
+	last_active: &mut Instant,
+) -> Result<bool, String> {
+	stream
+		.set_nonblocking(false)
+		.map_err(|_| "Set blocking failed")?;
+	let mut writer = stream.try_clone().map_err(|_| "Clone stream failed")?;
+	let mut reader = BufReader::new(DeadlineReader {
+		stream,
+		deadline: read_deadline(config, *last_active),
+	});
+
+	loop {
+		let mut line = String::new();
+		let read = reader.read_line(&mut line);
+		if should_stop(config, interrupted, *last_active) {
+			return Ok(true);
+		}
+		match read {
+			Ok(0) => return Ok(false),
+			Ok(_) => (),
+			// idle or slow client, disconnect to serve the others
+			Err(_) => return Ok(false),
+		}
+		*last_active = Instant::now();
+		let request: Result<AgentRequest, _> = serde_json::from_str(&line);
+		let (response, locked) = match request {
+			Ok(AgentRequest::List) => (to_response(Ok(list(key_pair_list))), false),
+			Ok(AgentRequest::Sign {
+				public_key,
+				payload,
+			}) => (
+				to_response(sign(key_pair_list, public_key, payload, config.confirm)),
+				false,
+			),
+			Ok(AgentRequest::Lock) => (to_response(Ok("Ok")), true),
+			Err(_) => (to_response::<()>(Err("Invalid request".to_string())), false),
+		};
+		writer
+			.write_all(format!("{}\n", response).as_bytes())
+			.map_err(|_| "Write failed")?;
+		if locked {
+			return Ok(true);
+		}
+		reader.get_mut().deadline = read_deadline(config, *last_active);
+	}
+}
+
+/// The deadline of reading a request: the read timeout, or the lock deadline if earlier
+fn read_deadline(config: &AgentConfig, last_active: Instant) -> Instant {
+	let deadline = Instant::now() + READ_TIMEOUT;
+	match lock_deadline(config, last_active) {
+		Some(lock_deadline) if lock_deadline < deadline => lock_deadline,
+		_ => deadline,
+	}
+}
+
+/// Stream failing reads once the deadline passes, however slowly the client sends
+struct DeadlineReader {
+	stream: UnixStream,
+	deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let now = Instant::now();
+		if now >= self.deadline {
+			return Err(io::Error::new(
+				io::ErrorKind::TimedOut,
+				"Read deadline passed",
+			));
+		}
+		self.stream.set_read_timeout(Some(self.deadline - now))?;
+		self.stream.read(buf)
+	}
+}
+
+fn to_response<T: Serialize>(result: Result<T, String>) -> String {
+	let response = match result {
+		Ok(result) => AgentResponse {
+			result: Some(result),
+			error: None,
+		},
+		Err(e) => AgentResponse {
+			result: None,
+			error: Some(e),
+		},
+	};
+	serde_json::to_string(&response).expect("qed")
+}
+
+fn list(key_pair_list: &[KeyPair]) -> Vec<Hex> {
+	key_pair_list
+		.iter()
+		.map(|key_pair| key_pair.public_key().to_vec().into())
+		.collect()
+}
+
+fn sign(
+	key_pair_list: &[KeyPair],
+	public_key: Hex,
+	payload: Hex,
+	confirm: bool,
+) -> Result<Hex, String> {
+	let public_key: Vec<u8> = public_key.into();
+	let payload: Vec<u8> = payload.into();
+
+	for key_pair in key_pair_list {
+		if key_pair.public_key().to_vec() != public_key {
+			continue;
+		}
+		if confirm && !confirm_sign(&public_key, &payload)? {
+			return Err("Sign refused".to_string());
+		}
+		let signature = key_pair.sign(&payload, SIGNING_CTX);
+		return Ok(signature.to_vec().into());
+	}
+
+	Err("Public key not loaded".to_string())
+}
+
+fn confirm_sign(public_key: &[u8], payload: &[u8]) -> Result<bool, String> {
+	let mut tty = std::fs::OpenOptions::new()
+		.read(true)
+		.write(true)
+		.open("/dev/tty")
+		.map_err(|_| "Open tty failed")?;
+	let public_key: String = Hex::from(public_key.to_vec()).into();
+	let payload: String = Hex::from(payload.to_vec()).into();
+	write!(
+		tty,
+		"Sign request\npublic key: {}\npayload: {}\nAllow? [y/N]: ",
+		public_key, payload
+	)
+	.map_err(|_| "Write tty failed")?;
+
+	let mut answer = String::new();
+	BufReader::new(tty)
+		.read_line(&mut answer)
+		.map_err(|_| "Read tty failed")?;
+
+	Ok(answer.trim().eq_ignore_ascii_case("y"))
+}
+
+fn request<T: for<'de> Deserialize<'de>>(
+	socket_path: &str,
+	request: &AgentRequest,
+) -> Result<T, String> {
+	let mut stream =
+		UnixStream::connect(socket_path).map_err(|e| format!("Connect agent failed: {:?}", e))?;
+	let request = serde_json::to_string(request).map_err(|_| "Json encode failed")?;
+	stream
+		.write_all(format!("{}\n", request).as_bytes())
+		.map_err(|_| "Write failed")?;
+
+	let mut line = String::new();
+	BufReader::new(stream)
+		.read_line(&mut line)
+		.map_err(|_| "Read failed")?;
+
+	let response: AgentResponse<T> =
+		serde_json::from_str(&line).map_err(|_| "Invalid agent response")?;
+
+	match (response.result, response.error) {
+		(_, Some(error)) => Err(error),
+		(Some(result), None) => Ok(result),
+		(None, None) => Err("Invalid agent response".to_string()),
+	}
+}
+
+/// List the public keys loaded in the agent
+pub fn agent_list(socket_path: &str) -> Result<Vec<Vec<u8>>, String> {
+	let result: Vec<Hex> = request(socket_path, &AgentRequest::List)?;
+	Ok(result.into_iter().map(Into::into).collect())
+}
+
+/// Sign payload by the agent
+pub fn agent_sign(socket_path: &str, public_key: &[u8], payload: &[u8]) -> Result<Vec<u8>, String> {
+	let result: Hex = request(
+		socket_path,
+		&AgentRequest::Sign {
+			public_key: public_key.to_vec().into(),
+			payload: payload.to_vec().into(),
+		},
+	)?;
+	Ok(result.into())
+}
+
+/// Lock the agent
+pub fn agent_lock(socket_path: &str) -> Result<(), String> {
+	let _result: String = request(socket_path, &AgentRequest::Lock)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::modules::account::verify_signature;
+
+	use super::*;
+
+	const MINI_SECRET_KEY: &str =
+		"0xfac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e";
+	const PUBLIC_KEY: &str = "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+
+	fn config() -> AgentConfig {
+		AgentConfig {
+			socket_path: String::new(),
+			timeout: None,
+			confirm: false,
+		}
+	}
+
+	fn send(client: &mut BufReader<UnixStream>, request: &str) -> String {
+		client
+			.get_mut()
+			.write_all(format!("{}\n", request).as_bytes())
+			.unwrap();
+		let mut line = String::new();
+		client.read_line(&mut line).unwrap();
+		line.trim_end().to_string()
+	}
+
+	#[test]
+	fn test_handle_stream() {
+		let (server, client) = UnixStream::pair().unwrap();
+		let handle = std::thread::spawn(move || {
+			let mini_secret_key: Vec<u8> = MINI_SECRET_KEY.parse::<Hex>().unwrap().into();
+			let key_pair_list = vec![KeyPair::from_mini_secret_key(&mini_secret_key).unwrap()];
+			handle_stream(
+				server,
+				&key_pair_list,
+				&config(),
+				&AtomicBool::new(false),
+				&mut Instant::now(),
+			)
+		});
+
+		let mut client = BufReader::new(client);
+		assert_eq!(
+			send(&mut client, r#"{"method":"list"}"#),
+			format!(r#"{{"result":["{}"]}}"#, PUBLIC_KEY)
+		);
+		assert_eq!(
+			send(&mut client, "not json"),
+			r#"{"error":"Invalid request"}"#
+		);
+		assert_eq!(
+			send(
+				&mut client,
+				r#"{"method":"sign","params":{"public_key":"0x00","payload":"0x00"}}"#
+			),
+			r#"{"error":"Public key not loaded"}"#
+		);
+
+		let response = send(
+			&mut client,
+			&format!(
+				r#"{{"method":"sign","params":{{"public_key":"{}","payload":"0x010203"}}}}"#,
+				PUBLIC_KEY
+			),
+		);
+		let response: AgentResponse<Hex> = serde_json::from_str(&response).unwrap();
+		let signature: Vec<u8> = response.result.unwrap().into();
+		let public_key: Vec<u8> = PUBLIC_KEY.parse::<Hex>().unwrap().into();
+		assert!(verify_signature(&public_key, &[1, 2, 3], &signature).unwrap());
+
+		assert_eq!(
+			send(&mut client, r#"{"method":"lock"}"#),
+			r#"{"result":"Ok"}"#
+		);
+		assert_eq!(handle.join().unwrap(), Ok(true));
+	}
+
+	#[test]
+	fn test_deadline_reader() {
+		let (server, mut client) = UnixStream::pair().unwrap();
+		let mut reader = DeadlineReader {
+			stream: server,
+			deadline: Instant::now() + Duration::from_millis(200),
+		};
+		let mut buf = [0u8; 8];
+
+		client.write_all(b"ab").unwrap();
+		assert_eq!(reader.read(&mut buf).unwrap(), 2);
+
+		// a silent client is cut off at the deadline
+		let start = Instant::now();
+		assert!(reader.read(&mut buf).is_err());
+		assert!(start.elapsed() < Duration::from_secs(2));
+
+		// and so is a client still sending once the deadline passed
+		client.write_all(b"cd").unwrap();
+		let err = reader.read(&mut buf).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+	}
+}
@@ -0,0 +1,733 @@
+use clap::{Arg, ArgMatches, SubCommand};
+use parity_codec::{Compact, Decode, Encode};
+use serde::Serialize;
+use yee_primitives::{Address, AddressCodec, Hrp};
+use yee_signer::tx::call::Call;
+
+use crate::modules::account::{get_shard_list, shard_count_list, Shard};
+use crate::modules::base::{get_rpc, get_shard_rpc_map};
+use crate::modules::state::{
+	get_map_storage_key, get_storage, get_value_storage_key, u128_from_slice,
+};
+use crate::modules::tx::{
+	assets, compose_args, compose_call, get_call_names, CallNames, CallPreview, ComposeOutput,
+};
+use crate::modules::{base, Command, Module};
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Asset tools".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	let mut app = SubCommand::with_name("asset").about("Asset tools");
+	for sub_command in sub_commands() {
+		app = app.subcommand(sub_command.app);
+	}
+	let f = run;
+
+	vec![Command { app, f }]
+}
+
+fn run(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	base::run(matches, || sub_commands(), || commands())
+}
+
+fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("issue")
+				.about("Compose tx to issue asset")
+				.args(&compose_args())
+				.arg(
+					Arg::with_name("NAME")
+						.long("name")
+						.help("Asset name")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("TOTAL")
+						.long("total")
+						.short("t")
+						.help("Total supply")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("DECIMALS")
+						.long("decimals")
+						.short("d")
+						.help("Decimals: 8 for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PREVIEW")
+						.long("preview")
+						.help("Preview the decoded call without composing tx")
+						.required(false),
+				),
+			f: issue,
+		},
+		Command {
+			app: SubCommand::with_name("transfer")
+				.about("Compose tx to transfer asset")
+				.args(&compose_args())
+				.arg(
+					Arg::with_name("ASSET_SHARD")
+						.long("asset-shard")
+						.short("s")
+						.help("Shard number where the asset is issued")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("ID")
+						.long("id")
+						.short("i")
+						.help("Asset id")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("DEST")
+						.long("dest")
+						.short("d")
+						.help("Dest address")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("AMOUNT")
+						.long("amount")
+						.short("m")
+						.help("Amount")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("PREVIEW")
+						.long("preview")
+						.help("Preview the decoded call without composing tx")
+						.required(false),
+				),
+			f: transfer,
+		},
+		Command {
+			app: SubCommand::with_name("balance")
+				.about("Get asset balance")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the shard of the account")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.help("Block number")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("ID")
+						.long("id")
+						.short("i")
+						.help("Asset id")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("ADDRESS")
+						.help("Address")
+						.required(true)
+						.index(1),
+				),
+			f: balance,
+		},
+		Command {
+			app: SubCommand::with_name("supply")
+				.about("Get asset total supply")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the asset shard")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("ASSET_SHARD")
+						.long("asset-shard")
+						.short("s")
+						.help("Shard number where the asset is issued: route by --shard-rpc or the rpc of the profile")
+						.takes_value(true)
+						.conflicts_with("RPC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.help("Block number")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("ID")
+						.long("id")
+						.short("i")
+						.help("Asset id")
+						.takes_value(true)
+						.required(true),
+				),
+			f: supply,
+		},
+		Command {
+			app: SubCommand::with_name("list")
+				.about("List assets")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the asset shard")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("ASSET_SHARD")
+						.long("asset-shard")
+						.short("s")
+						.help("Shard number where the asset is issued: route by --shard-rpc or the rpc of the profile")
+						.takes_value(true)
+						.conflicts_with("RPC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.help("Block number")
+						.takes_value(true)
+						.required(false),
+				),
+			f: list,
+		},
+	]
+}
+
+const DEFAULT_DECIMALS: u16 = 8;
+
+fn issue(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let name = matches.value_of("NAME").expect("qed");
+
+	let total = matches
+		.value_of("TOTAL")
+		.expect("qed")
+		.parse::<u128>()
+		.map_err(|_| "Invalid total")?;
+
+	let decimals = match matches.value_of("DECIMALS") {
+		Some(decimals) => decimals.parse::<u16>().map_err(|_| "Invalid decimals")?,
+		None => DEFAULT_DECIMALS,
+	};
+
+	let call = build_assets_call(matches, assets::Call::Issue(assets::Issue {
+		name: name.as_bytes().to_vec(),
+		total: Compact(total),
+		decimals,
+	}))?;
+
+	compose_assets_call(matches, call)
+}
+
+fn transfer(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let asset_shard = matches
+		.value_of("ASSET_SHARD")
+		.expect("qed")
+		.parse::<u16>()
+		.map_err(|_| "Invalid asset shard")?;
+
+	let id = matches
+		.value_of("ID")
+		.expect("qed")
+		.parse::<u32>()
+		.map_err(|_| "Invalid asset id")?;
+
+	let dest = matches.value_of("DEST").expect("qed");
+	let (dest, _hrp) = <[u8; 32]>::from_address(&Address(dest.to_string()))
+		.map_err(|_| "Address decode failed")?;
+
+	let amount = matches
+		.value_of("AMOUNT")
+		.expect("qed")
+		.parse::<u128>()
+		.map_err(|_| "Invalid amount")?;
+
+	let target = {
+		let mut account = vec![0xffu8];
+		account.extend_from_slice(&dest);
+		Decode::decode(&mut &account[..]).ok_or("Invalid dest")?
+	};
+
+	let call = build_assets_call(matches, assets::Call::Transfer(assets::Transfer {
+		shard_code: asset_shard.encode(),
+		id: Compact(id),
+		target,
+		amount: Compact(amount),
+	}))?;
+
+	compose_assets_call(matches, call)
+}
+
+fn balance(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let id = matches
+		.value_of("ID")
+		.expect("qed")
+		.parse::<u32>()
+		.map_err(|_| "Invalid asset id")?;
+
+	let address = matches.value_of("ADDRESS").expect("qed");
+	let (public_key, _hrp) = <[u8; 32]>::from_address(&Address(address.to_string()))
+		.map_err(|_| "Address decode failed")?;
+
+	let rpc = &base::get_rpc_by_public_key(matches, &public_key)?;
+
+	let number = get_number(matches)?;
+
+	let storage_key = get_map_storage_key(&(id, public_key).encode(), b"Assets Balances");
+
+	let balance = match get_storage(rpc, storage_key, number)? {
+		Some(data) => u128_from_slice(&data)?,
+		None => 0,
+	};
+
+	#[derive(Serialize)]
+	struct Output {
+		id: u32,
+		address: String,
+		testnet_address: String,
+		shard: Vec<Shard>,
+		balance: u128,
+	}
+
+	let output = Output {
+		id,
+		address: public_key
+			.to_address(Hrp::MAINNET)
+			.map_err(|_| "Address encode failed")?
+			.0,
+		testnet_address: public_key
+			.to_address(Hrp::TESTNET)
+			.map_err(|_| "Address encode failed")?
+			.0,
+		shard: get_shard_list(&public_key, &shard_count_list()?)?,
+		balance,
+	};
+
+	base::output(output)
+}
+
+fn supply(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_asset_rpc(matches)?;
+
+	let id = matches
+		.value_of("ID")
+		.expect("qed")
+		.parse::<u32>()
+		.map_err(|_| "Invalid asset id")?;
+
+	let number = get_number(matches)?;
+
+	let total_supply = get_total_supply(rpc, id, number)?;
+
+	base::output(total_supply)
+}
+
+fn list(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_asset_rpc(matches)?;
+
+	let number = get_number(matches)?;
+
+	let storage_key = get_value_storage_key(b"Assets NextAssetId");
+
+	let next_asset_id = match get_storage(rpc, storage_key, number)? {
+		Some(data) => u32::decode(&mut &data[..]).ok_or("Invalid next asset id")?,
+		None => 0,
+	};
+
+	#[derive(Serialize)]
+	struct Item {
+		id: u32,
+		total_supply: u128,
+	}
+
+	let mut items = vec![];
+	for id in 0..next_asset_id {
+		if let Some(total_supply) = get_total_supply(rpc, id, number)? {
+			items.push(Item { id, total_supply });
+		}
+	}
+
+	base::output(items)
+}
+
+/// Get rpc of the asset shard: --rpc, or route --asset-shard by --shard-rpc or the rpc of the profile
+fn get_asset_rpc(matches: &ArgMatches) -> Result<String, String> {
+	let asset_shard = match matches.value_of("ASSET_SHARD") {
+		Some(asset_shard) => asset_shard
+			.parse::<u16>()
+			.map_err(|_| "Invalid asset shard")?,
+		None => return get_rpc(matches),
+	};
+
+	let shard_rpc_map = get_shard_rpc_map(matches)?;
+
+	let rpc = shard_rpc_map
+		.get(&asset_shard)
+		.ok_or(format!("No rpc for shard {}", asset_shard))?;
+
+	Ok(rpc.clone())
+}
+
+fn get_number(matches: &ArgMatches) -> Result<Option<u64>, String> {
+	match matches.value_of("NUMBER") {
+		Some(number) => Ok(Some(number.parse::<u64>().map_err(|_| "Invalid block number")?)),
+		None => Ok(None),
+	}
+}
+
+fn get_total_supply(rpc: &str, id: u32, number: Option<u64>) -> Result<Option<u128>, String> {
+	let storage_key = get_map_storage_key(&id.encode(), b"Assets TotalSupply");
+
+	match get_storage(rpc, storage_key, number)? {
+		Some(data) => Ok(Some(u128_from_slice(&data)?)),
+		None => Ok(None),
+	}
+}
+
+/// Compose tx of the call, or output the call only if --preview
+fn compose_assets_call(matches: &ArgMatches, call: Call) -> Result<Vec<String>, String> {
+	if matches.is_present("PREVIEW") {
+		let output = CallPreview {
+			raw: call.encode().into(),
+			call: call.into(),
+		};
+		return base::output(output);
+	}
+
+	let output = ComposeOutput {
+		compose: compose_call(matches, call.clone())?,
+		call: call.into(),
+	};
+
+	base::output(output)
+}
+
+/// Build call from the assets call of the hack module, only if encoded back to the same bytes
+fn build_assets_call(matches: &ArgMatches, call: assets::Call) -> Result<Call, String> {
+	// preview offline by the built-in names, unless --rpc given
+	let call_names = if matches.is_present("PREVIEW") && !matches.is_present("RPC") {
+		CallNames::offline()
+	} else {
+		get_call_names(matches)?
+	};
+
+	let mut raw = vec![call_names.parse_module("assets")?];
+	raw.extend(call.encode());
+	let call: Call = Decode::decode(&mut &raw[..]).ok_or("Invalid assets call")?;
+	if call.encode() != raw {
+		return Err("Invalid assets call".to_string());
+	}
+	Ok(call)
+}
+
+mod cases {
+	use linked_hash_map::LinkedHashMap;
+
+	use crate::modules::Case;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![(
+			"asset",
+			vec![
+				Case {
+					desc: "Compose tx to transfer asset".to_string(),
+					input: vec![
+						"transfer",
+						"-r",
+						"http://localhost:9033",
+						"-k",
+						"0xa8666e483fd6c26dbb6deeec5afae765561ecc94df432f02920fc5d9cd4ae206ead577e5bc11215d4735cee89218e22f2d950a2a4667745ea1b5ea8b26bba5d6",
+						"-s",
+						"0",
+						"-i",
+						"0",
+						"-d",
+						"yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+						"-m",
+						"1000",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![r#"{
+  "result": {
+    "shard_num": 0,
+    "shard_count": 4,
+    "sender_address": "yee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqmtaf9v",
+    "sender_testnet_address": "tyee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqkv6lyl",
+    "nonce": 1,
+    "period": 64,
+    "current": 80,
+    "current_hash": "0x7b7e5e6fa8a8d5d1a8d1b8f0b2c7d8e8e5b1b1a1c7e6a9c4c4a3f0a2e1b2c3d4",
+    "raw": "0x...",
+    "call": {
+      "Assets": {
+        "Transfer": {
+          "shard_code": "0x0000",
+          "id": 0,
+          "target": "0xff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d",
+          "target_address": "yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+          "target_testnet_address": "tyee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7sa3q30q",
+          "target_shard": [
+            {
+              "shard_num": 1,
+              "shard_count": 4
+            },
+            {
+              "shard_num": 5,
+              "shard_count": 8
+            }
+          ],
+          "amount": 1000
+        }
+      }
+    }
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Preview asset issue call".to_string(),
+					input: vec!["issue", "--preview", "--name", "TEST", "-t", "100000000"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![r#"{
+  "result": {
+    "call": {
+      "Assets": {
+        "Issue": {
+          "name": "TEST",
+          "total": 100000000,
+          "decimals": 8
+        }
+      }
+    },
+    "raw": "0x080010544553540284d7170800"
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Preview asset transfer call".to_string(),
+					input: vec![
+						"transfer",
+						"--preview",
+						"-s",
+						"0",
+						"-i",
+						"0",
+						"-d",
+						"yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+						"-m",
+						"1000",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![r#"{
+  "result": {
+    "call": {
+      "Assets": {
+        "Transfer": {
+          "shard_code": "0x0000",
+          "id": 0,
+          "target": "0xff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d",
+          "target_address": "yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+          "target_testnet_address": "tyee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7sa3q30q",
+          "target_shard": [
+            {
+              "shard_num": 1,
+              "shard_count": 4
+            },
+            {
+              "shard_num": 5,
+              "shard_count": 8
+            }
+          ],
+          "amount": 1000
+        }
+      }
+    },
+    "raw": "0x080108000000ff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3da10f"
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Get asset balance".to_string(),
+					input: vec![
+						"balance",
+						"-r",
+						"http://localhost:9033",
+						"-i",
+						"0",
+						"yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![r#"{
+  "result": {
+    "id": 0,
+    "address": "yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+    "testnet_address": "tyee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7sa3q30q",
+    "shard": [
+      {
+        "shard_num": 1,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 5,
+        "shard_count": 8
+      }
+    ],
+    "balance": 1000
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Get asset total supply in the asset shard".to_string(),
+					input: vec![
+						"supply",
+						"--shard-rpc",
+						"0=http://localhost:9033",
+						"--shard-rpc",
+						"1=http://localhost:19033",
+						"-s",
+						"0",
+						"-i",
+						"0",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![r#"{
+  "result": 100000000
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "List assets".to_string(),
+					input: vec!["list", "-r", "http://localhost:9033"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![r#"{
+  "result": [
+    {
+      "id": 0,
+      "total_supply": 100000000
+    }
+  ]
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+			],
+		)]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::modules::base::test::test_module;
+
+	use super::*;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io;
 use std::io::{BufRead, Read, Write};
 use std::str::FromStr;
@@ -9,7 +9,10 @@ use serde::{
 	de::{self, DeserializeOwned},
 	Deserialize, Deserializer, Serialize, Serializer,
 };
+use yee_sharding_primitives::utils::shard_num_for_bytes;
 
+use crate::modules::config::{self, OutputFormat};
+use crate::modules::meter::{get_block_info, Number};
 use crate::modules::Command;
 
 #[allow(dead_code)]
@@ -37,7 +40,7 @@ pub fn input_bytes(matches: &ArgMatches) -> Result<Vec<u8>, String> {
 }
 
 pub fn output<T: Serialize>(t: T) -> Result<Vec<String>, String> {
-	let output = serde_json::to_string_pretty(&Output {
+	let output = to_json_string(&Output {
 		result: Some(t),
 		error: None,
 	})
@@ -53,17 +56,99 @@ pub fn output_error(s: String) -> String {
 			message: s,
 		}),
 	};
-	let output = serde_json::to_string_pretty(&output).expect("qed");
+	let output = to_json_string(&output).expect("qed");
 	output
 }
 
-pub fn get_rpc(matches: &ArgMatches) -> String {
-	let rpc = matches.value_of("RPC").expect("qed");
-	let rpc = match rpc.parse::<u32>() {
+fn to_json_string<T: Serialize>(t: &T) -> serde_json::Result<String> {
+	match config::output_format() {
+		OutputFormat::Pretty => serde_json::to_string_pretty(t),
+		OutputFormat::Compact => serde_json::to_string(t),
+	}
+}
+
+/// Get rpc from --rpc, or the first rpc of the profile
+pub fn get_rpc(matches: &ArgMatches) -> Result<String, String> {
+	let rpc = match matches.value_of("RPC") {
+		Some(rpc) => rpc.to_string(),
+		None => config::active_profile()?
+			.and_then(|x| x.profile.rpc.into_iter().next())
+			.ok_or("RPC address required: use --rpc or a profile")?,
+	};
+	Ok(parse_rpc(&rpc))
+}
+
+/// Get rpc of the shard of the account: --rpc, or route by --shard-rpc or the rpc of the profile
+pub fn get_rpc_by_public_key(matches: &ArgMatches, public_key: &[u8]) -> Result<String, String> {
+	if let Some(rpc) = matches.value_of("RPC") {
+		return Ok(parse_rpc(rpc));
+	}
+
+	let shard_rpc_map = get_shard_rpc_map(matches)?;
+
+	let any_rpc = shard_rpc_map
+		.values()
+		.next()
+		.ok_or("RPC address required: use --rpc, --shard-rpc or a profile")?;
+
+	let shard_count = get_block_info(Number::Best, any_rpc)?
+		.shard
+		.ok_or("Invalid shard info")?
+		.shard_count;
+
+	let shard_num = shard_num_for_bytes(public_key, shard_count).ok_or("Invalid shard count")?;
+
+	let rpc = shard_rpc_map
+		.get(&shard_num)
+		.ok_or(format!("No rpc for shard {}", shard_num))?;
+
+	Ok(rpc.clone())
+}
+
+/// Shard rpc map from --shard-rpc (shard_num=rpc), or the rpc of the profile (indexed by shard number)
+pub fn get_shard_rpc_map(matches: &ArgMatches) -> Result<BTreeMap<u16, String>, String> {
+	let shard_rpc_map = match matches.values_of("SHARD_RPC") {
+		Some(shard_rpc_list) => shard_rpc_list
+			.map(|x| {
+				let mut split = x.splitn(2, '=');
+				let (shard_num, rpc) = match (split.next(), split.next()) {
+					(Some(shard_num), Some(rpc)) => (shard_num, rpc),
+					_ => return Err(format!("Invalid shard rpc: {}", x)),
+				};
+				let shard_num = shard_num
+					.trim()
+					.parse::<u16>()
+					.map_err(|_| format!("Invalid shard rpc: {}", x))?;
+				Ok((shard_num, parse_rpc(rpc.trim())))
+			})
+			.collect::<Result<BTreeMap<_, _>, String>>()?,
+		None => config::active_profile()?
+			.map(|x| x.profile.rpc)
+			.unwrap_or_default()
+			.into_iter()
+			.enumerate()
+			.map(|(shard_num, rpc)| (shard_num as u16, parse_rpc(&rpc)))
+			.collect(),
+	};
+	Ok(shard_rpc_map)
+}
+
+/// Port only for localhost
+pub fn parse_rpc(rpc: &str) -> String {
+	match rpc.parse::<u32>() {
 		Ok(port) => format!("http://localhost:{}", port),
 		Err(_) => format!("{}", rpc),
-	};
-	rpc
+	}
+}
+
+/// Get keystore path from --keystore-path, or the keystore path of the profile
+pub fn get_keystore_path(matches: &ArgMatches) -> Result<String, String> {
+	match matches.value_of("KEYSTORE_PATH") {
+		Some(keystore_path) => Ok(keystore_path.to_string()),
+		None => config::active_profile()?
+			.and_then(|x| x.profile.keystore_path)
+			.ok_or("Keystore path required: use --keystore-path or a profile".to_string()),
+	}
 }
 
 #[derive(Serialize, Deserialize)]
@@ -213,6 +298,9 @@ pub struct RpcError {
 	pub message: String,
 }
 
+/// JSON-RPC error code of the method not supported by the node
+pub const METHOD_NOT_FOUND: i32 = -32601;
+
 pub fn put_to_file(content: &[u8], file_path: &str) -> Result<(), String> {
 	let mut file = std::fs::File::create(file_path).map_err(|_| "File creation failed")?;
 	file.write_all(content).map_err(|_| "Write failed")?;
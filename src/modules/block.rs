@@ -36,9 +36,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				Arg::with_name("RPC")
 					.long("rpc")
 					.short("r")
-					.help("RPC address")
+					.help("RPC address: the profile for default")
 					.takes_value(true)
-					.required(true),
+					.required(false),
 			)
 			.arg(
 				Arg::with_name("HASH")
@@ -66,7 +66,7 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 }
 
 fn search(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 
 	let best_number = get_block_info(Number::Best, rpc)?.number;
 
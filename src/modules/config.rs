@@ -0,0 +1,365 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::{ArgMatches, SubCommand};
+use lazy_static::lazy_static;
+use mut_static::MutStatic;
+use serde::{Deserialize, Serialize};
+
+use crate::modules::{base, Command, Module};
+
+pub const CONFIG_DIR: &'static str = "yee-utils";
+pub const CONFIG_FILE: &'static str = "config.toml";
+
+lazy_static! {
+	static ref CONFIG_SOURCE: MutStatic<ConfigSource> = MutStatic::new();
+	static ref ACTIVE_PROFILE: MutStatic<Option<ActiveProfile>> = MutStatic::new();
+}
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Config tools".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	let mut app = SubCommand::with_name("config").about("Config tools");
+	for sub_command in sub_commands() {
+		app = app.subcommand(sub_command.app);
+	}
+	let f = run;
+
+	vec![Command { app, f }]
+}
+
+fn run(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	base::run(matches, || sub_commands(), || commands())
+}
+
+fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("show").about("Show the active profile"),
+			f: show,
+		},
+		Command {
+			app: SubCommand::with_name("list").about("List profiles in config file"),
+			f: list,
+		},
+	]
+}
+
+fn show(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let config_path = get_config_path(matches)?;
+
+	let active_profile = active_profile()?;
+
+	#[derive(Serialize)]
+	struct Output {
+		config_path: String,
+		profile_name: Option<String>,
+		profile: Option<Profile>,
+	}
+
+	let output = Output {
+		config_path: config_path.to_string_lossy().to_string(),
+		profile_name: active_profile.as_ref().map(|x| x.name.clone()),
+		profile: active_profile.map(|x| x.profile),
+	};
+
+	base::output(output)
+}
+
+fn list(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let config_path = get_config_path(matches)?;
+
+	let config = load_config(&config_path)?.unwrap_or_default();
+
+	#[derive(Serialize)]
+	struct Output {
+		config_path: String,
+		default_profile: Option<String>,
+		profiles: BTreeMap<String, Profile>,
+	}
+
+	let output = Output {
+		config_path: config_path.to_string_lossy().to_string(),
+		default_profile: config.default_profile,
+		profiles: config.profiles,
+	};
+
+	base::output(output)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Config {
+	pub default_profile: Option<String>,
+	#[serde(default)]
+	pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Profile {
+	/// RPC endpoints, indexed by shard number
+	#[serde(default)]
+	pub rpc: Vec<String>,
+	pub keystore_path: Option<String>,
+	pub output: Option<OutputFormat>,
+	pub shard_counts: Option<Vec<u16>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+	Pretty,
+	Compact,
+}
+
+#[derive(Clone, Debug)]
+pub struct ActiveProfile {
+	pub name: String,
+	pub profile: Profile,
+}
+
+/// --config and --profile, to load the profile when first read
+#[derive(Clone, Debug, Default)]
+struct ConfigSource {
+	config_path: Option<String>,
+	profile_name: Option<String>,
+}
+
+/// Keep --config and --profile: the config file is loaded only when a command reads the profile,
+/// so a malformed config file fails only those commands
+pub fn init(matches: &ArgMatches) -> Result<(), String> {
+	CONFIG_SOURCE
+		.set(ConfigSource {
+			config_path: matches.value_of("CONFIG").map(ToString::to_string),
+			profile_name: matches.value_of("PROFILE").map(ToString::to_string),
+		})
+		.map_err(|_| "Failed to set config source")?;
+	Ok(())
+}
+
+/// The profile selected by --profile or default_profile, None if neither
+pub fn active_profile() -> Result<Option<ActiveProfile>, String> {
+	if let Ok(active_profile) = ACTIVE_PROFILE.read() {
+		return Ok(active_profile.clone());
+	}
+	let active_profile = load_active_profile()?;
+	ACTIVE_PROFILE
+		.set(active_profile.clone())
+		.map_err(|_| "Failed to set profile")?;
+	Ok(active_profile)
+}
+
+fn load_active_profile() -> Result<Option<ActiveProfile>, String> {
+	let source = match CONFIG_SOURCE.read() {
+		Ok(source) => source.clone(),
+		Err(_) => ConfigSource::default(),
+	};
+
+	let config_path = config_path(source.config_path.as_ref().map(String::as_str))?;
+
+	let config = load_config(&config_path)?;
+
+	let profile_name = match source.profile_name {
+		Some(profile_name) => Some(profile_name),
+		None => config.as_ref().and_then(|x| x.default_profile.clone()),
+	};
+
+	let profile_name = match profile_name {
+		Some(profile_name) => profile_name,
+		None => return Ok(None),
+	};
+
+	let profile = config
+		.and_then(|mut x| x.profiles.remove(&profile_name))
+		.ok_or(format!("Profile not found: {}", profile_name))?;
+
+	if let Some(shard_counts) = &profile.shard_counts {
+		if shard_counts.is_empty() || shard_counts.iter().any(|x| !x.is_power_of_two()) {
+			return Err("Invalid shard counts in profile: should be power of 2".to_string());
+		}
+	}
+
+	Ok(Some(ActiveProfile {
+		name: profile_name,
+		profile,
+	}))
+}
+
+/// The output format of the profile, pretty if the config file is malformed to output the error
+pub fn output_format() -> OutputFormat {
+	active_profile()
+		.ok()
+		.and_then(|x| x)
+		.and_then(|x| x.profile.output)
+		.unwrap_or(OutputFormat::Pretty)
+}
+
+fn get_config_path(matches: &ArgMatches) -> Result<PathBuf, String> {
+	config_path(matches.value_of("CONFIG"))
+}
+
+/// --config, or $XDG_CONFIG_HOME/yee-utils/config.toml, or ~/.config/yee-utils/config.toml
+fn config_path(config_path: Option<&str>) -> Result<PathBuf, String> {
+	if let Some(config_path) = config_path {
+		return Ok(PathBuf::from(config_path));
+	}
+	let config_home = match std::env::var_os("XDG_CONFIG_HOME") {
+		Some(config_home) => PathBuf::from(config_home),
+		None => {
+			let home = std::env::var_os("HOME").ok_or("Unknown home dir")?;
+			PathBuf::from(home).join(".config")
+		}
+	};
+	Ok(config_home.join(CONFIG_DIR).join(CONFIG_FILE))
+}
+
+fn load_config(config_path: &PathBuf) -> Result<Option<Config>, String> {
+	if !config_path.exists() {
+		return Ok(None);
+	}
+	let content = base::get_from_file(&config_path.to_string_lossy())?;
+	let content = String::from_utf8(content).map_err(|_| "Invalid config file")?;
+	let config: Config =
+		toml::from_str(&content).map_err(|e| format!("Invalid config file: {}", e))?;
+	Ok(Some(config))
+}
+
+mod cases {
+	use crate::modules::Case;
+	use linked_hash_map::LinkedHashMap;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![(
+			"config",
+			vec![
+				Case {
+					desc: "Show the active profile".to_string(),
+					input: vec!["show", "--profile", "testnet"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": {
+    "config_path": "/home/user/.config/yee-utils/config.toml",
+    "profile_name": "testnet",
+    "profile": {
+      "rpc": [
+        "http://localhost:9033",
+        "http://localhost:19033",
+        "http://localhost:29033",
+        "http://localhost:39033"
+      ],
+      "keystore_path": "keystore.dat",
+      "output": "pretty",
+      "shard_counts": [
+        4
+      ]
+    }
+  }
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "List profiles in config file".to_string(),
+					input: vec!["list"].into_iter().map(Into::into).collect(),
+					output: vec![
+						r#"{
+  "result": {
+    "config_path": "/home/user/.config/yee-utils/config.toml",
+    "default_profile": "local",
+    "profiles": {
+      "local": {
+        "rpc": [
+          "http://localhost:9033"
+        ],
+        "keystore_path": null,
+        "output": "compact",
+        "shard_counts": null
+      }
+    }
+  }
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+			],
+		)]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use clap::Arg;
+
+	use crate::modules::base::test::test_module;
+
+	use super::*;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+
+	#[test]
+	fn test_list() {
+		let app = SubCommand::with_name("list")
+			.arg(Arg::with_name("CONFIG").long("config").takes_value(true));
+		let matches = app.get_matches_from(vec!["list", "--config", "./res/config.toml"]);
+
+		let output = list(&matches).unwrap();
+		let output: serde_json::Value = serde_json::from_str(&output[0]).unwrap();
+		let expected = serde_json::json!({
+			"result": {
+				"config_path": "./res/config.toml",
+				"default_profile": "local",
+				"profiles": {
+					"local": {
+						"rpc": ["http://localhost:9033"],
+						"keystore_path": null,
+						"output": "compact",
+						"shard_counts": null
+					},
+					"testnet": {
+						"rpc": [
+							"http://localhost:9033",
+							"http://localhost:19033",
+							"http://localhost:29033",
+							"http://localhost:39033"
+						],
+						"keystore_path": "keystore.dat",
+						"output": null,
+						"shard_counts": [4]
+					}
+				}
+			}
+		});
+		assert_eq!(output, expected);
+	}
+
+	#[test]
+	fn test_load_config() {
+		assert!(load_config(&PathBuf::from("./res/not_found.toml"))
+			.unwrap()
+			.is_none());
+		assert!(load_config(&PathBuf::from("./res/dev_keystore.json")).is_err());
+	}
+}
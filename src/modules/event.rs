@@ -42,9 +42,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				Arg::with_name("RPC")
 					.long("rpc")
 					.short("r")
-					.help("RPC address")
+					.help("RPC address: the profile for default")
 					.takes_value(true)
-					.required(true),
+					.required(false),
 			)
 			.arg(
 				Arg::with_name("KEYWORD")
@@ -73,7 +73,7 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 }
 
 fn search(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 
 	let best_number = get_block_info(Number::Best, rpc)?.number;
 
@@ -143,7 +143,7 @@ fn accept_item(item: &SearchItem, keyword: Option<&str>) -> bool {
 	true
 }
 
-fn get_block_events(rpc: &str, block_hash: &[u8]) -> Result<Vec<String>, String> {
+pub fn get_block_events(rpc: &str, block_hash: &[u8]) -> Result<Vec<String>, String> {
 	let mut runtime = Runtime::new().expect("qed");
 
 	let block_hash: Hex = block_hash.to_vec().into();
@@ -1,5 +1,6 @@
 use core::num::NonZeroU32;
 use std::str::FromStr;
+use std::time::Duration;
 
 use clap::{Arg, ArgMatches, SubCommand};
 use crypto::aes::{ctr, KeySize};
@@ -12,8 +13,13 @@ use ring::pbkdf2::derive;
 use serde::{Deserialize, Serialize};
 use sss_rs::wrapped_sharing::share;
 use yee_signer::KeyPair;
+use zeroize::Zeroizing;
 
-use crate::modules::account::{desc_public_key, generate_account, random_32_bytes};
+use crate::modules::account::{
+	desc_public_key, generate_account, get_message, message_args, mini_secret_key_from_mnemonic,
+	random_32_bytes, sign_message, DescPublicKeyOutput,
+};
+use crate::modules::agent::{self, AgentConfig};
 use crate::modules::base::Hex;
 use crate::modules::{base, Command, Module};
 
@@ -93,9 +99,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("KEYSTORE_PATH")
 						.long("keystore-path")
 						.short("k")
-						.help("Keystore path")
+						.help("Keystore path: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
 				),
 			f: desc,
 		},
@@ -125,6 +131,13 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.help("Require password keepers")
 						.takes_value(true)
 						.required(true),
+				)
+				.arg(
+					Arg::with_name("MNEMONIC")
+						.long("mnemonic")
+						.short("m")
+						.help("Import from mnemonic phrase instead of secret key")
+						.required(false),
 				),
 			f: import,
 		},
@@ -135,9 +148,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("KEYSTORE_PATH")
 						.long("keystore-path")
 						.short("k")
-						.help("Keystore path")
+						.help("Keystore path: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("NEW_KEYSTORE_PATH")
@@ -165,6 +178,117 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				),
 			f: export,
 		},
+		Command {
+			app: SubCommand::with_name("info")
+				.about("Show keystore file info without password")
+				.arg(
+					Arg::with_name("KEYSTORE_PATH")
+						.long("keystore-path")
+						.short("k")
+						.help("Keystore path: the profile for default")
+						.takes_value(true)
+						.required(false),
+				),
+			f: info,
+		},
+		Command {
+			app: SubCommand::with_name("verify")
+				.about("Verify a single password of keystore file")
+				.arg(
+					Arg::with_name("KEYSTORE_PATH")
+						.long("keystore-path")
+						.short("k")
+						.help("Keystore path: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("INDEX")
+						.long("index")
+						.short("i")
+						.help("Share index: hex, check the password against the given share")
+						.takes_value(true)
+						.required(false),
+				),
+			f: verify,
+		},
+		Command {
+			app: SubCommand::with_name("sign")
+				.about("Sign message by keystore file")
+				.arg(
+					Arg::with_name("KEYSTORE_PATH")
+						.long("keystore-path")
+						.short("k")
+						.help("Keystore path: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.args(&message_args())
+				.arg(Arg::with_name("INPUT").required(false).index(1)),
+			f: sign,
+		},
+		Command {
+			app: SubCommand::with_name("agent")
+				.about("Unlock keystore files and serve signing on a unix socket")
+				.arg(
+					Arg::with_name("KEYSTORE_PATH")
+						.long("keystore-path")
+						.short("k")
+						.help("Keystore path")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("SOCKET")
+						.long("socket")
+						.short("s")
+						.help("Unix socket path")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("TIMEOUT")
+						.long("timeout")
+						.help("Idle timeout in seconds: auto lock after idle, 0 for never")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("CONFIRM")
+						.long("confirm")
+						.help("Ask for confirmation on each sign request")
+						.required(false),
+				),
+			f: agent,
+		},
+		Command {
+			app: SubCommand::with_name("agent_list")
+				.about("List public keys loaded in the agent")
+				.arg(
+					Arg::with_name("SOCKET")
+						.long("socket")
+						.short("s")
+						.help("Unix socket path")
+						.takes_value(true)
+						.required(true),
+				),
+			f: agent_list,
+		},
+		Command {
+			app: SubCommand::with_name("agent_lock")
+				.about("Lock the agent")
+				.arg(
+					Arg::with_name("SOCKET")
+						.long("socket")
+						.short("s")
+						.help("Unix socket path")
+						.takes_value(true)
+						.required(true),
+				),
+			f: agent_lock,
+		},
 	]
 }
 
@@ -208,7 +332,7 @@ fn generate(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn desc(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let keystore_path = matches.value_of("KEYSTORE_PATH").expect("qed");
+	let keystore_path = &base::get_keystore_path(matches)?;
 	let secret_key = get_keystore(keystore_path)?;
 
 	let key_pair = KeyPair::from_secret_key(&secret_key)?;
@@ -240,11 +364,20 @@ fn import(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		.parse::<u8>()
 		.map_err(|_| "Invalid require")?;
 
-	let prompt = format!("Secret key: ");
-	let secret_key = rpassword::read_password_from_tty(Some(&prompt)).unwrap();
-	let secret_key: Vec<u8> = Hex::from_str(&secret_key)?.into();
+	let (key_pair, secret_key) = if matches.is_present("MNEMONIC") {
+		let prompt = format!("Mnemonic: ");
+		let mnemonic = rpassword::read_password_from_tty(Some(&prompt)).unwrap();
+		let mini_secret_key = mini_secret_key_from_mnemonic(&mnemonic)?;
+		let key_pair = KeyPair::from_mini_secret_key(&mini_secret_key)?;
+		let secret_key = key_pair.secret_key().to_vec();
+		(key_pair, secret_key)
+	} else {
+		let prompt = format!("Secret key: ");
+		let secret_key = rpassword::read_password_from_tty(Some(&prompt)).unwrap();
+		let secret_key: Vec<u8> = Hex::from_str(&secret_key)?.into();
+		(KeyPair::from_secret_key(&secret_key)?, secret_key)
+	};
 
-	let key_pair = KeyPair::from_secret_key(&secret_key)?;
 	let public_key = key_pair.public_key();
 
 	put_keystore(&public_key, &secret_key, total, require, keystore_path)?;
@@ -253,7 +386,7 @@ fn import(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn export(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let keystore_path = matches.value_of("KEYSTORE_PATH").expect("qed");
+	let keystore_path = &base::get_keystore_path(matches)?;
 
 	let new_keystore_path = matches.value_of("NEW_KEYSTORE_PATH").expect("qed");
 
@@ -288,6 +421,189 @@ fn export(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	base::output("Ok")
 }
 
+fn info(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let keystore_path = &base::get_keystore_path(matches)?;
+
+	let keystore = load_keystore(keystore_path)?;
+
+	let public_key: Vec<u8> = keystore.public_key.clone().into();
+
+	let account = desc_public_key(public_key)?;
+
+	let mut share_index_list = keystore
+		.share_list
+		.keys()
+		.cloned()
+		.collect::<Vec<String>>();
+	share_index_list.sort();
+
+	#[derive(Serialize)]
+	struct Kdf {
+		algorithm: String,
+		iterations: u32,
+		salt: Hex,
+		salt_prefix: String,
+	}
+
+	#[derive(Serialize)]
+	struct Output {
+		version: String,
+		#[serde(flatten)]
+		account: DescPublicKeyOutput,
+		total: usize,
+		require: u8,
+		share_index_list: Vec<String>,
+		cipher: String,
+		index_kdf: Kdf,
+		key_kdf: Kdf,
+	}
+
+	let output = Output {
+		version: keystore.version,
+		account,
+		total: keystore.share_list.len(),
+		require: keystore.require,
+		share_index_list,
+		cipher: CIPHER.to_string(),
+		index_kdf: Kdf {
+			algorithm: KDF_ALGORITHM.to_string(),
+			iterations: INDEX_ITERATIONS,
+			salt: keystore.index_salt,
+			salt_prefix: String::from_utf8_lossy(INDEX_SALT_PREFIX).to_string(),
+		},
+		key_kdf: Kdf {
+			algorithm: KDF_ALGORITHM.to_string(),
+			iterations: KEY_ITERATIONS,
+			salt: keystore.key_salt,
+			salt_prefix: String::from_utf8_lossy(KEY_SALT_PREFIX).to_string(),
+		},
+	};
+
+	base::output(output)
+}
+
+fn verify(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let keystore_path = &base::get_keystore_path(matches)?;
+
+	let expected_index: Option<String> = match matches.value_of("INDEX") {
+		Some(index) => Some(Hex::from_str(index)?.into()),
+		None => None,
+	};
+
+	let keystore = load_keystore(keystore_path)?;
+
+	let prompt = format!("Password: ");
+	let password = rpassword::read_password_from_tty(Some(&prompt)).unwrap();
+
+	let index_salt: Vec<u8> = keystore.index_salt.into();
+	let key_salt: Vec<u8> = keystore.key_salt.into();
+
+	let index: Hex = password_to_index(&password, &index_salt).into();
+	let index: String = index.into();
+
+	let share = keystore.share_list.get(&index);
+
+	let valid = match (&expected_index, share) {
+		(Some(expected_index), Some(_)) => expected_index == &index,
+		(None, Some(_)) => true,
+		(_, None) => false,
+	};
+
+	// a single share keystore holds the secret key itself, so it can be fully checked
+	let valid = match (valid, share, keystore.share_list.len()) {
+		(true, Some(share), 1) => {
+			let share: Vec<u8> = share.clone().into();
+			let secret_key = aes_dec(&share, &password, &key_salt)?;
+			let key_pair = KeyPair::from_secret_key(&secret_key)?;
+			let public_key: Hex = key_pair.public_key().to_vec().into();
+			public_key == keystore.public_key
+		}
+		(valid, _, _) => valid,
+	};
+
+	#[derive(Serialize)]
+	struct Output {
+		index: Option<String>,
+		valid: bool,
+	}
+
+	let output = Output {
+		index: if valid { Some(index) } else { None },
+		valid,
+	};
+
+	base::output(output)
+}
+
+fn sign(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let keystore_path = &base::get_keystore_path(matches)?;
+
+	let message = get_message(matches)?;
+
+	let secret_key = Zeroizing::new(get_keystore(keystore_path)?);
+
+	let key_pair = KeyPair::from_secret_key(&secret_key)?;
+
+	let output = sign_message(&key_pair, message)?;
+
+	base::output(output)
+}
+
+fn agent(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let keystore_path_list = matches.values_of("KEYSTORE_PATH").expect("qed");
+
+	let socket_path = matches.value_of("SOCKET").expect("qed");
+
+	let timeout = match matches.value_of("TIMEOUT") {
+		Some(timeout) => timeout.parse::<u64>().map_err(|_| "Invalid timeout")?,
+		None => DEFAULT_AGENT_TIMEOUT,
+	};
+	let timeout = match timeout {
+		0 => None,
+		timeout => Some(Duration::from_secs(timeout)),
+	};
+
+	let confirm = matches.is_present("CONFIRM");
+
+	let mut secret_key_list = vec![];
+	for keystore_path in keystore_path_list {
+		println!("Unlock keystore file: {}", keystore_path);
+		secret_key_list.push(Zeroizing::new(get_keystore(keystore_path)?));
+	}
+
+	println!("Agent listening on: {}", socket_path);
+
+	agent::serve(
+		secret_key_list,
+		AgentConfig {
+			socket_path: socket_path.to_string(),
+			timeout,
+			confirm,
+		},
+	)?;
+
+	base::output("Locked")
+}
+
+fn agent_list(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let socket_path = matches.value_of("SOCKET").expect("qed");
+
+	let output = agent::agent_list(socket_path)?
+		.into_iter()
+		.map(desc_public_key)
+		.collect::<Result<Vec<_>, String>>()?;
+
+	base::output(output)
+}
+
+fn agent_lock(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let socket_path = matches.value_of("SOCKET").expect("qed");
+
+	agent::agent_lock(socket_path)?;
+
+	base::output("Ok")
+}
+
 #[derive(Serialize, Deserialize)]
 struct Keystore {
 	version: String,
@@ -298,13 +614,25 @@ struct Keystore {
 	require: u8,
 }
 
-fn put_keystore(
+pub fn put_keystore(
 	public_key: &[u8],
 	secret_key: &[u8],
 	total: u8,
 	require: u8,
 	keystore_path: &str,
 ) -> Result<(), String> {
+	let password_list = read_password_list(total);
+
+	put_keystore_with_password_list(
+		public_key,
+		secret_key,
+		password_list,
+		require,
+		keystore_path,
+	)
+}
+
+pub fn read_password_list(total: u8) -> Vec<String> {
 	let mut password_list: Vec<String> = vec![];
 	for i in 0..total {
 		let password = loop {
@@ -320,6 +648,17 @@ fn put_keystore(
 		};
 		password_list.push(password);
 	}
+	password_list
+}
+
+pub fn put_keystore_with_password_list(
+	public_key: &[u8],
+	secret_key: &[u8],
+	password_list: Vec<String>,
+	require: u8,
+	keystore_path: &str,
+) -> Result<(), String> {
+	let total = password_list.len() as u8;
 	let secret = secret_key.to_vec();
 
 	let share_list = match total {
@@ -369,7 +708,7 @@ fn put_keystore(
 	Ok(())
 }
 
-pub fn get_keystore(keystore_path: &str) -> Result<Vec<u8>, String> {
+fn load_keystore(keystore_path: &str) -> Result<Keystore, String> {
 	let content = base::get_from_file(keystore_path)?;
 
 	let keystore: Keystore =
@@ -379,6 +718,12 @@ pub fn get_keystore(keystore_path: &str) -> Result<Vec<u8>, String> {
 		return Err("Invalid keystore version".to_string());
 	}
 
+	Ok(keystore)
+}
+
+pub fn get_keystore(keystore_path: &str) -> Result<Vec<u8>, String> {
+	let keystore = load_keystore(keystore_path)?;
+
 	let mut password_list: Vec<String> = vec![];
 	for i in 0..keystore.require {
 		let prompt = format!("Password ({}/{}): ", i + 1, keystore.require);
@@ -436,6 +781,9 @@ pub fn get_keystore(keystore_path: &str) -> Result<Vec<u8>, String> {
 
 const KEYSTORE_VERSION: &'static str = "1.0";
 const KEY_SIZE: KeySize = KeySize::KeySize256;
+const CIPHER: &'static str = "aes-256-ctr";
+const DEFAULT_AGENT_TIMEOUT: u64 = 900;
+const KDF_ALGORITHM: &'static str = "pbkdf2-sha256";
 
 pub fn aes_enc(plain: &[u8], password: &str, salt: &[u8]) -> Result<Vec<u8>, String> {
 	let (key, iv) = password_to_key(&password, salt);
@@ -571,6 +919,225 @@ mod cases {
 					is_test: false,
 					since: "0.1.0".to_string(),
 				},
+				Case {
+					desc: "Show keystore file info".to_string(),
+					input: vec!["info", "-k", "./keystore.json"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": {
+    "version": "1.0",
+    "public_key": "0x68f84b8652acc98505827f092b3652ad8329f5a3e504ccdf29f1c44d1538b578",
+    "address": "yee1druyhpjj4nyc2pvz0uyjkdjj4kpjnadru5zvehef78zy69fck4uq2vaymh",
+    "testnet_address": "tyee1druyhpjj4nyc2pvz0uyjkdjj4kpjnadru5zvehef78zy69fck4uq8t6j6y",
+    "shard": [
+      {
+        "shard_num": 0,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 0,
+        "shard_count": 8
+      }
+    ],
+    "total": 3,
+    "require": 2,
+    "share_index_list": [
+      "0x1f0b3e6e0b5a9d7c",
+      "0x8c2d7a41f6e0b3a5",
+      "0xd4e5f60718293a4b"
+    ],
+    "cipher": "aes-256-ctr",
+    "index_kdf": {
+      "algorithm": "pbkdf2-sha256",
+      "iterations": 32,
+      "salt": "0x3c1a5e0e7bd1b8e5b4e7e4f8c1a2d7f05a9e1c0b6d3f2a4e8c7b9d0e1f2a3b4c",
+      "salt_prefix": "yee-utils-password-index"
+    },
+    "key_kdf": {
+      "algorithm": "pbkdf2-sha256",
+      "iterations": 1024,
+      "salt": "0x9a8b7c6d5e4f30211203f4e5d6c7b8a9f0e1d2c3b4a5968778695a4b3c2d1e0f",
+      "salt_prefix": "yee-utils-password-key"
+    }
+  }
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Show dev keystore file info".to_string(),
+					input: vec!["info", "-k", "./res/dev_keystore.json"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": {
+    "version": "1.0",
+    "public_key": "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+    "address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "testnet_address": "tyee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aqzckf7l",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ],
+    "total": 1,
+    "require": 1,
+    "share_index_list": [
+      "0xb5206691300f2da7"
+    ],
+    "cipher": "aes-256-ctr",
+    "index_kdf": {
+      "algorithm": "pbkdf2-sha256",
+      "iterations": 32,
+      "salt": "0xf1d72f49d0e26741cc5c7965d84610f34ed7f49504ba34ec32f54c8d65973100",
+      "salt_prefix": "yee-utils-password-index"
+    },
+    "key_kdf": {
+      "algorithm": "pbkdf2-sha256",
+      "iterations": 1024,
+      "salt": "0x3c886e45391c946902175ff38c7b830092446430a023029b6380ac88850d8448",
+      "salt_prefix": "yee-utils-password-key"
+    }
+  }
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: false,
+					is_test: true,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Verify a single password of keystore file".to_string(),
+					input: vec!["verify", "-k", "./keystore.json"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": {
+    "index": "0x8c2d7a41f6e0b3a5",
+    "valid": true
+  }
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Sign message by keystore file".to_string(),
+					input: vec!["sign", "-k", "./dev_keystore.json", "-d", "yee-deposit:", "hello"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": {
+    "message": "0x7965652d6465706f7369743a68656c6c6f",
+    "signature": "0xbeee46ee9f25462c5bb6a4a8a3662d6765e1530a2d317d2a9c5c122689a5ab4b732787f1a08c5253b4f232169801c74efa887d4c9c7df2230af286c2de7d2b07",
+    "public_key": "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a",
+    "address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "testnet_address": "tyee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aqzckf7l",
+    "shard": [
+      {
+        "shard_num": 2,
+        "shard_count": 4
+      },
+      {
+        "shard_num": 2,
+        "shard_count": 8
+      }
+    ]
+  }
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Start signing agent".to_string(),
+					input: vec![
+						"agent",
+						"-k",
+						"./keystore.json",
+						"-s",
+						"/tmp/yee-agent.sock",
+						"--timeout",
+						"600",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![
+						r#"{
+  "result": "Locked"
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "List public keys loaded in the agent".to_string(),
+					input: vec!["agent_list", "-s", "/tmp/yee-agent.sock"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": [
+    {
+      "public_key": "0x68f84b8652acc98505827f092b3652ad8329f5a3e504ccdf29f1c44d1538b578",
+      "address": "yee1druyhpjj4nyc2pvz0uyjkdjj4kpjnadru5zvehef78zy69fck4uq2vaymh",
+      "testnet_address": "tyee1druyhpjj4nyc2pvz0uyjkdjj4kpjnadru5zvehef78zy69fck4uq8t6j6y",
+      "shard": [
+        {
+          "shard_num": 0,
+          "shard_count": 4
+        },
+        {
+          "shard_num": 0,
+          "shard_count": 8
+        }
+      ]
+    }
+  ]
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
 				Case {
 					desc: "Import account and save to keystore file".to_string(),
 					input: vec!["import", "-k", "./keystore.json"]
@@ -589,6 +1156,24 @@ mod cases {
 					is_test: false,
 					since: "0.1.0".to_string(),
 				},
+				Case {
+					desc: "Import account from mnemonic and save to keystore file".to_string(),
+					input: vec!["import", "-k", "./keystore.json", "-t", "3", "-r", "2", "--mnemonic"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![
+						r#"{
+  "result": "Ok"
+}"#,
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
 				Case {
 					desc: "Export account and save to a new keystore file".to_string(),
 					input: vec![
@@ -0,0 +1,753 @@
+use std::str::FromStr;
+
+use parity_codec::{Compact, Decode, Encode};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use substrate_primitives::{blake2_128, blake2_256};
+use tokio::runtime::Runtime;
+use yee_primitives::{AddressCodec, Hrp};
+
+use crate::modules::account::parse_public_key;
+use crate::modules::base::{self, Hex};
+use crate::modules::state::{twox, twox_128, twox_256};
+
+/// 'meta' in little endian, the prefix of runtime metadata
+pub const META_RESERVED: u32 = 0x6174656d;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Metadata {
+	pub version: u8,
+	pub modules: Vec<ModuleMetadata>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModuleMetadata {
+	pub name: String,
+	pub prefix: String,
+	pub storage: Vec<StorageMetadata>,
+	/// None if the module has no call type, which takes no call index
+	pub calls: Option<Vec<FunctionMetadata>>,
+	/// None if the module has no event type, which takes no event index
+	pub events: Option<Vec<EventMetadata>>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StorageMetadata {
+	pub name: String,
+	pub modifier: StorageModifier,
+	pub ty: StorageType,
+	pub default: Hex,
+	pub documentation: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub enum StorageType {
+	Plain {
+		value: String,
+	},
+	Map {
+		hasher: StorageHasher,
+		key: String,
+		value: String,
+		is_linked: bool,
+	},
+	DoubleMap {
+		hasher: StorageHasher,
+		key1: String,
+		key2: String,
+		value: String,
+		key2_hasher: StorageHasher,
+	},
+}
+
+#[derive(Decode, Serialize, Clone, Debug)]
+pub enum StorageModifier {
+	Optional,
+	Default,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Decode, Serialize, Clone, Copy, Debug)]
+pub enum StorageHasher {
+	Blake2_128,
+	Blake2_256,
+	Twox128,
+	Twox256,
+	Twox64Concat,
+}
+
+#[derive(Decode, Serialize, Clone, Debug)]
+pub struct FunctionMetadata {
+	pub name: String,
+	pub arguments: Vec<FunctionArgumentMetadata>,
+	pub documentation: Vec<String>,
+}
+
+#[derive(Decode, Serialize, Clone, Debug)]
+pub struct FunctionArgumentMetadata {
+	pub name: String,
+	pub ty: String,
+}
+
+#[derive(Decode, Serialize, Clone, Debug)]
+pub struct EventMetadata {
+	pub name: String,
+	pub arguments: Vec<String>,
+	pub documentation: Vec<String>,
+}
+
+// hack srml-metadata v3: maps are hashed by blake2_256
+mod v3 {
+	use super::{Decode, EventMetadata, FunctionMetadata, StorageModifier};
+
+	#[derive(Decode)]
+	pub struct ModuleMetadata {
+		pub name: String,
+		pub prefix: String,
+		pub storage: Option<Vec<StorageFunctionMetadata>>,
+		pub calls: Option<Vec<FunctionMetadata>>,
+		pub event: Option<Vec<EventMetadata>>,
+	}
+
+	#[derive(Decode)]
+	pub struct StorageFunctionMetadata {
+		pub name: String,
+		pub modifier: StorageModifier,
+		pub ty: StorageFunctionType,
+		pub default: Vec<u8>,
+		pub documentation: Vec<String>,
+	}
+
+	#[derive(Decode)]
+	pub enum StorageFunctionType {
+		Plain(String),
+		Map {
+			key: String,
+			value: String,
+			is_linked: bool,
+		},
+		DoubleMap {
+			key1: String,
+			key2: String,
+			value: String,
+			key2_hasher: String,
+		},
+	}
+}
+
+// hack srml-metadata v4: maps are hashed by the given hasher
+mod v4 {
+	use super::{Decode, EventMetadata, FunctionMetadata, StorageHasher, StorageModifier};
+
+	#[derive(Decode)]
+	pub struct ModuleMetadata {
+		pub name: String,
+		pub prefix: String,
+		pub storage: Option<Vec<StorageFunctionMetadata>>,
+		pub calls: Option<Vec<FunctionMetadata>>,
+		pub event: Option<Vec<EventMetadata>>,
+	}
+
+	#[derive(Decode)]
+	pub struct StorageFunctionMetadata {
+		pub name: String,
+		pub modifier: StorageModifier,
+		pub ty: StorageFunctionType,
+		pub default: Vec<u8>,
+		pub documentation: Vec<String>,
+	}
+
+	#[derive(Decode)]
+	pub enum StorageFunctionType {
+		Plain(String),
+		Map {
+			hasher: StorageHasher,
+			key: String,
+			value: String,
+			is_linked: bool,
+		},
+		DoubleMap {
+			hasher: StorageHasher,
+			key1: String,
+			key2: String,
+			value: String,
+			key2_hasher: StorageHasher,
+		},
+	}
+}
+
+impl From<v3::ModuleMetadata> for ModuleMetadata {
+	fn from(t: v3::ModuleMetadata) -> Self {
+		let storage = t
+			.storage
+			.unwrap_or_default()
+			.into_iter()
+			.map(|x| {
+				let ty = match x.ty {
+					v3::StorageFunctionType::Plain(value) => StorageType::Plain { value },
+					v3::StorageFunctionType::Map {
+						key,
+						value,
+						is_linked,
+					} => StorageType::Map {
+						hasher: StorageHasher::Blake2_256,
+						key,
+						value,
+						is_linked,
+					},
+					v3::StorageFunctionType::DoubleMap {
+						key1,
+						key2,
+						value,
+						key2_hasher,
+					} => StorageType::DoubleMap {
+						hasher: StorageHasher::Blake2_256,
+						key1,
+						key2,
+						value,
+						key2_hasher: StorageHasher::from_name(&key2_hasher),
+					},
+				};
+				StorageMetadata {
+					name: x.name,
+					modifier: x.modifier,
+					ty,
+					default: x.default.into(),
+					documentation: x.documentation,
+				}
+			})
+			.collect();
+		ModuleMetadata {
+			name: t.name,
+			prefix: t.prefix,
+			storage,
+			calls: t.calls,
+			events: t.event,
+		}
+	}
+}
+
+impl From<v4::ModuleMetadata> for ModuleMetadata {
+	fn from(t: v4::ModuleMetadata) -> Self {
+		let storage = t
+			.storage
+			.unwrap_or_default()
+			.into_iter()
+			.map(|x| {
+				let ty = match x.ty {
+					v4::StorageFunctionType::Plain(value) => StorageType::Plain { value },
+					v4::StorageFunctionType::Map {
+						hasher,
+						key,
+						value,
+						is_linked,
+					} => StorageType::Map {
+						hasher,
+						key,
+						value,
+						is_linked,
+					},
+					v4::StorageFunctionType::DoubleMap {
+						hasher,
+						key1,
+						key2,
+						value,
+						key2_hasher,
+					} => StorageType::DoubleMap {
+						hasher,
+						key1,
+						key2,
+						value,
+						key2_hasher,
+					},
+				};
+				StorageMetadata {
+					name: x.name,
+					modifier: x.modifier,
+					ty,
+					default: x.default.into(),
+					documentation: x.documentation,
+				}
+			})
+			.collect();
+		ModuleMetadata {
+			name: t.name,
+			prefix: t.prefix,
+			storage,
+			calls: t.calls,
+			events: t.event,
+		}
+	}
+}
+
+impl StorageHasher {
+	/// The hasher name of srml-metadata v3 double map
+	fn from_name(name: &str) -> Self {
+		match name {
+			"blake2_128" => StorageHasher::Blake2_128,
+			"twox_128" => StorageHasher::Twox128,
+			"twox_256" => StorageHasher::Twox256,
+			"twox_64_concat" => StorageHasher::Twox64Concat,
+			_ => StorageHasher::Blake2_256,
+		}
+	}
+
+	pub fn hash(&self, data: &[u8]) -> Vec<u8> {
+		match self {
+			StorageHasher::Blake2_128 => blake2_128(data).to_vec(),
+			StorageHasher::Blake2_256 => blake2_256(data).to_vec(),
+			StorageHasher::Twox128 => twox_128(data),
+			StorageHasher::Twox256 => twox_256(data),
+			StorageHasher::Twox64Concat => {
+				let mut result = twox(data, 0);
+				result.extend_from_slice(data);
+				result
+			}
+		}
+	}
+}
+
+impl Metadata {
+	pub fn decode(data: &[u8]) -> Result<Self, String> {
+		let input = &mut &data[..];
+		let reserved = u32::decode(input).ok_or("Invalid metadata")?;
+		if reserved != META_RESERVED {
+			return Err("Invalid metadata".to_string());
+		}
+		let version = u8::decode(input).ok_or("Invalid metadata")?;
+		let modules: Vec<ModuleMetadata> = match version {
+			3 => Vec::<v3::ModuleMetadata>::decode(input)
+				.ok_or("Invalid metadata")?
+				.into_iter()
+				.map(Into::into)
+				.collect(),
+			4 => Vec::<v4::ModuleMetadata>::decode(input)
+				.ok_or("Invalid metadata")?
+				.into_iter()
+				.map(Into::into)
+				.collect(),
+			_ => return Err(format!("Unsupported metadata version: {}", version)),
+		};
+		Ok(Metadata { version, modules })
+	}
+
+	/// Find the storage by module name (or prefix) and storage name
+	pub fn storage(
+		&self,
+		module: &str,
+		name: &str,
+	) -> Result<(&ModuleMetadata, &StorageMetadata), String> {
+		let module = self
+			.modules
+			.iter()
+			.find(|x| x.name == module || x.prefix == module)
+			.or_else(|| {
+				self.modules.iter().find(|x| {
+					x.name.eq_ignore_ascii_case(module) || x.prefix.eq_ignore_ascii_case(module)
+				})
+			})
+			.ok_or(format!("Module not found: {}", module))?;
+		let storage = module
+			.storage
+			.iter()
+			.find(|x| x.name == name)
+			.or_else(|| {
+				module
+					.storage
+					.iter()
+					.find(|x| x.name.eq_ignore_ascii_case(name))
+			})
+			.ok_or(format!("Storage not found: {} {}", module.name, name))?;
+		Ok((module, storage))
+	}
+}
+
+impl StorageMetadata {
+	/// Storage key by the encoded keys, the same as get_value_storage_key and get_map_storage_key
+	pub fn storage_key(&self, prefix: &str, keys: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+		let mut prefix = format!("{} {}", prefix, self.name).into_bytes();
+		match (&self.ty, keys) {
+			(StorageType::Plain { .. }, []) => Ok(twox_128(&prefix)),
+			(StorageType::Map { hasher, .. }, [key]) => {
+				prefix.extend_from_slice(key);
+				Ok(hasher.hash(&prefix))
+			}
+			(
+				StorageType::DoubleMap {
+					hasher,
+					key2_hasher,
+					..
+				},
+				[key1, key2],
+			) => {
+				prefix.extend_from_slice(key1);
+				let mut result = hasher.hash(&prefix);
+				result.extend(key2_hasher.hash(key2));
+				Ok(result)
+			}
+			_ => Err(format!("Invalid key count for storage: {}", self.name)),
+		}
+	}
+
+	pub fn key_types(&self) -> Vec<&str> {
+		match &self.ty {
+			StorageType::Plain { .. } => vec![],
+			StorageType::Map { key, .. } => vec![key.as_str()],
+			StorageType::DoubleMap { key1, key2, .. } => vec![key1.as_str(), key2.as_str()],
+		}
+	}
+
+	pub fn value_type(&self) -> &str {
+		match &self.ty {
+			StorageType::Plain { value } => value.as_str(),
+			StorageType::Map { value, .. } => value.as_str(),
+			StorageType::DoubleMap { value, .. } => value.as_str(),
+		}
+	}
+}
+
+pub fn get_metadata(rpc: &str, block_hash: Option<String>) -> Result<Metadata, String> {
+	let mut runtime = Runtime::new().expect("qed");
+
+	let result = runtime
+		.block_on(base::rpc_call::<_, String>(
+			rpc,
+			"state_getMetadata",
+			&(block_hash,),
+		))?
+		.result
+		.ok_or("Get metadata failed")?;
+
+	let data: Vec<u8> = Hex::from_str(&result)?.into();
+
+	Metadata::decode(&data)
+}
+
+/// Type of storage key and value, parsed from the type name in metadata
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+	Bool,
+	U8,
+	U16,
+	U32,
+	U64,
+	U128,
+	AccountId,
+	Hash,
+	Bytes,
+	Compact(Box<Type>),
+	Vec(Box<Type>),
+	Option(Box<Type>),
+	Tuple(Vec<Type>),
+	Array(Box<Type>, usize),
+	Unknown(String),
+}
+
+impl Type {
+	pub fn parse(name: &str) -> Type {
+		let re = Regex::new(r"<T as [A-Za-z0-9_:]+>::").expect("qed");
+		let name = re.replace_all(name, "");
+		let name = name.replace("T::", "").replace(" ", "");
+		Self::parse_normalized(&name)
+	}
+
+	fn parse_normalized(name: &str) -> Type {
+		match name {
+			"bool" => return Type::Bool,
+			"u8" => return Type::U8,
+			"u16" => return Type::U16,
+			"u32" | "AssetId" => return Type::U32,
+			"u64" | "Index" | "BlockNumber" | "Moment" => return Type::U64,
+			"u128" | "Balance" => return Type::U128,
+			"AccountId" => return Type::AccountId,
+			"Hash" | "H256" => return Type::Hash,
+			"Vec<u8>" | "Bytes" => return Type::Bytes,
+			_ => (),
+		}
+		let inner = |prefix: &str| -> Option<Type> {
+			if name.starts_with(prefix) && name.ends_with('>') {
+				Some(Self::parse_normalized(&name[prefix.len()..name.len() - 1]))
+			} else {
+				None
+			}
+		};
+		if let Some(t) = inner("Compact<") {
+			return Type::Compact(Box::new(t));
+		}
+		if let Some(t) = inner("Vec<") {
+			return Type::Vec(Box::new(t));
+		}
+		if let Some(t) = inner("Option<") {
+			return Type::Option(Box::new(t));
+		}
+		if name.starts_with('(') && name.ends_with(')') {
+			let items = split_top_level(&name[1..name.len() - 1]);
+			return Type::Tuple(
+				items
+					.into_iter()
+					.map(|x| Self::parse_normalized(&x))
+					.collect(),
+			);
+		}
+		if name.starts_with('[') && name.ends_with(']') {
+			let body = &name[1..name.len() - 1];
+			if let Some(pos) = body.rfind(';') {
+				if let Ok(len) = body[pos + 1..].parse::<usize>() {
+					return Type::Array(Box::new(Self::parse_normalized(&body[..pos])), len);
+				}
+			}
+		}
+		Type::Unknown(name.to_string())
+	}
+
+	/// Decode value to json, account id to address
+	pub fn decode(&self, input: &mut &[u8], mainnet: bool) -> Result<Value, String> {
+		let value = match self {
+			Type::Bool => Value::Bool(bool::decode(input).ok_or("Decode bool failed")?),
+			Type::U8 => Value::from(u8::decode(input).ok_or("Decode u8 failed")?),
+			Type::U16 => Value::from(u16::decode(input).ok_or("Decode u16 failed")?),
+			Type::U32 => Value::from(u32::decode(input).ok_or("Decode u32 failed")?),
+			Type::U64 => Value::from(u64::decode(input).ok_or("Decode u64 failed")?),
+			Type::U128 => u128_value(u128::decode(input).ok_or("Decode u128 failed")?),
+			Type::Compact(inner) => match inner.as_ref() {
+				Type::U128 => u128_value(
+					Compact::<u128>::decode(input)
+						.ok_or("Decode compact failed")?
+						.0,
+				),
+				_ => Value::from(
+					Compact::<u64>::decode(input)
+						.ok_or("Decode compact failed")?
+						.0,
+				),
+			},
+			Type::AccountId => {
+				let public_key = read_bytes(input, 32)?;
+				let hrp = if mainnet { Hrp::MAINNET } else { Hrp::TESTNET };
+				let address = public_key
+					.to_address(hrp)
+					.map_err(|_| "Address encode failed")?;
+				Value::String(address.0)
+			}
+			Type::Hash => Value::String(Hex::from(read_bytes(input, 32)?).into()),
+			Type::Bytes => {
+				let bytes = Vec::<u8>::decode(input).ok_or("Decode bytes failed")?;
+				Value::String(Hex::from(bytes).into())
+			}
+			Type::Vec(inner) => {
+				let len = Compact::<u32>::decode(input).ok_or("Decode vec failed")?.0;
+				let items = (0..len)
+					.map(|_| inner.decode(input, mainnet))
+					.collect::<Result<Vec<_>, _>>()?;
+				Value::Array(items)
+			}
+			Type::Option(inner) => match u8::decode(input).ok_or("Decode option failed")? {
+				0 => Value::Null,
+				1 => inner.decode(input, mainnet)?,
+				_ => return Err("Decode option failed".to_string()),
+			},
+			Type::Tuple(items) => Value::Array(
+				items
+					.iter()
+					.map(|x| x.decode(input, mainnet))
+					.collect::<Result<Vec<_>, _>>()?,
+			),
+			Type::Array(inner, len) => match inner.as_ref() {
+				Type::U8 => Value::String(Hex::from(read_bytes(input, *len)?).into()),
+				_ => Value::Array(
+					(0..*len)
+						.map(|_| inner.decode(input, mainnet))
+						.collect::<Result<Vec<_>, _>>()?,
+				),
+			},
+			Type::Unknown(name) => return Err(format!("Unknown type: {}", name)),
+		};
+		Ok(value)
+	}
+
+	/// Decode integer value, used to calculate the delta of balances
+	pub fn decode_integer(&self, data: &[u8]) -> Option<u128> {
+		let input = &mut &data[..];
+		match self {
+			Type::U8 => u8::decode(input).map(Into::into),
+			Type::U16 => u16::decode(input).map(Into::into),
+			Type::U32 => u32::decode(input).map(Into::into),
+			Type::U64 => u64::decode(input).map(Into::into),
+			Type::U128 => u128::decode(input),
+			Type::Compact(_) => Compact::<u128>::decode(input).map(|x| x.0),
+			_ => None,
+		}
+	}
+
+	/// Encode key from command line input: number, address, or hex of the encoded key
+	pub fn encode(&self, input: &str) -> Result<Vec<u8>, String> {
+		let encoded = match self {
+			Type::Bool => input.parse::<bool>().map_err(|_| "Invalid bool")?.encode(),
+			Type::U8 => input.parse::<u8>().map_err(|_| "Invalid u8")?.encode(),
+			Type::U16 => input.parse::<u16>().map_err(|_| "Invalid u16")?.encode(),
+			Type::U32 => input.parse::<u32>().map_err(|_| "Invalid u32")?.encode(),
+			Type::U64 => input.parse::<u64>().map_err(|_| "Invalid u64")?.encode(),
+			Type::U128 => input.parse::<u128>().map_err(|_| "Invalid u128")?.encode(),
+			Type::Compact(_) => {
+				Compact(input.parse::<u128>().map_err(|_| "Invalid compact")?).encode()
+			}
+			Type::AccountId => parse_public_key(input)?,
+			Type::Bytes => {
+				let bytes: Vec<u8> = Hex::from_str(input)?.into();
+				bytes.encode()
+			}
+			_ => Hex::from_str(input)?.into(),
+		};
+		Ok(encoded)
+	}
+}
+
+fn u128_value(v: u128) -> Value {
+	if v <= u64::max_value() as u128 {
+		Value::from(v as u64)
+	} else {
+		Value::String(v.to_string())
+	}
+}
+
+fn read_bytes(input: &mut &[u8], len: usize) -> Result<Vec<u8>, String> {
+	if input.len() < len {
+		return Err("Not enough data".to_string());
+	}
+	let (head, tail) = input.split_at(len);
+	*input = tail;
+	Ok(head.to_vec())
+}
+
+/// Split by commas not in brackets
+fn split_top_level(s: &str) -> Vec<String> {
+	let mut result = vec![];
+	let mut depth = 0;
+	let mut current = String::new();
+	for c in s.chars() {
+		match c {
+			'<' | '(' | '[' => depth += 1,
+			'>' | ')' | ']' => depth -= 1,
+			',' if depth == 0 => {
+				result.push(current.clone());
+				current.clear();
+				continue;
+			}
+			_ => (),
+		}
+		current.push(c);
+	}
+	if !current.is_empty() {
+		result.push(current);
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::modules::state::{get_map_storage_key, get_value_storage_key};
+
+	use super::*;
+
+	const PUBLIC_KEY: &str = "0x46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a";
+	const ADDRESS: &str = "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv";
+
+	fn storage(name: &str, ty: StorageType) -> StorageMetadata {
+		StorageMetadata {
+			name: name.to_string(),
+			modifier: StorageModifier::Default,
+			ty,
+			default: Hex::from(vec![]),
+			documentation: vec![],
+		}
+	}
+
+	#[test]
+	fn test_storage_key() {
+		let free_balance = storage(
+			"FreeBalance",
+			StorageType::Map {
+				hasher: StorageHasher::Blake2_256,
+				key: "T::AccountId".to_string(),
+				value: "T::Balance".to_string(),
+				is_linked: false,
+			},
+		);
+		let public_key = Type::parse("T::AccountId").encode(ADDRESS).unwrap();
+		assert_eq!(
+			free_balance
+				.storage_key("Balances", &[public_key.clone()])
+				.unwrap(),
+			get_map_storage_key(&public_key, b"Balances FreeBalance").0
+		);
+
+		let total_issuance = storage(
+			"TotalIssuance",
+			StorageType::Plain {
+				value: "T::Balance".to_string(),
+			},
+		);
+		assert_eq!(
+			total_issuance.storage_key("Balances", &[]).unwrap(),
+			get_value_storage_key(b"Balances TotalIssuance").0
+		);
+
+		assert!(total_issuance
+			.storage_key("Balances", &[public_key])
+			.is_err());
+	}
+
+	#[test]
+	fn test_type_round_trip() {
+		let round_trip = |name: &str, data: Vec<u8>| {
+			let input = &mut &data[..];
+			let value = Type::parse(name).decode(input, true).unwrap();
+			assert!(input.is_empty(), "{}", name);
+			value
+		};
+
+		let encoded = Type::parse("T::Balance").encode("1000").unwrap();
+		assert_eq!(round_trip("T::Balance", encoded), Value::from(1000));
+
+		let encoded = Type::parse("u128").encode("100000000000000000000").unwrap();
+		assert_eq!(
+			round_trip("u128", encoded),
+			Value::String("100000000000000000000".to_string())
+		);
+
+		let encoded = Type::parse("Compact<T::Balance>")
+			.encode("100000000000000000000")
+			.unwrap();
+		assert_eq!(
+			round_trip("Compact<T::Balance>", encoded),
+			Value::String("100000000000000000000".to_string())
+		);
+
+		let encoded = Type::parse("Compact<u32>").encode("42").unwrap();
+		assert_eq!(round_trip("Compact<u32>", encoded), Value::from(42));
+
+		let encoded = vec![1u32, 2, 3].encode();
+		assert_eq!(
+			round_trip("Vec<u32>", encoded),
+			Value::Array(vec![Value::from(1), Value::from(2), Value::from(3)])
+		);
+
+		let encoded = Type::parse("T::AccountId").encode(ADDRESS).unwrap();
+		assert_eq!(
+			Hex::from(encoded.clone()),
+			Hex::from_str(PUBLIC_KEY).unwrap()
+		);
+		assert_eq!(
+			round_trip("T::AccountId", encoded.clone()),
+			Value::String(ADDRESS.to_string())
+		);
+
+		let mut tuple = 7u64.encode();
+		tuple.extend(encoded);
+		assert_eq!(
+			round_trip("(T::BlockNumber, T::AccountId)", tuple),
+			Value::Array(vec![Value::from(7), Value::String(ADDRESS.to_string())])
+		);
+	}
+}
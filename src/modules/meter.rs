@@ -36,9 +36,9 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				Arg::with_name("RPC")
 					.long("rpc")
 					.short("r")
-					.help("RPC address")
+					.help("RPC address: the profile for default")
 					.takes_value(true)
-					.required(true),
+					.required(false),
 			)
 			.arg(
 				Arg::with_name("BEST")
@@ -111,7 +111,7 @@ pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 }
 
 fn meter(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 
 	let mut enable_list = EnableList {
 		best: matches.is_present("BEST"),
@@ -424,16 +424,20 @@ pub fn get_block_info(number: Number, rpc: &str) -> Result<BlockInfo, String> {
 }
 
 pub fn get_hrp(rpc: &str) -> Result<Hrp, String> {
+	let hrp = match is_mainnet(rpc)? {
+		true => Hrp::MAINNET,
+		false => Hrp::TESTNET,
+	};
+
+	Ok(hrp)
+}
+
+pub fn is_mainnet(rpc: &str) -> Result<bool, String> {
 	let mut runtime = Runtime::new().expect("qed");
 
 	let chain_info = runtime.block_on(get_chain_info_async(rpc))?;
 
-	let hrp = match chain_info.as_str() {
-		"MainNet" => Hrp::MAINNET,
-		_ => Hrp::TESTNET,
-	};
-
-	Ok(hrp)
+	Ok(chain_info == "MainNet")
 }
 
 async fn get_chain_info_async(rpc: &str) -> Result<String, String> {
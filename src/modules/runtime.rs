@@ -0,0 +1,960 @@
+use clap::{Arg, ArgMatches, SubCommand};
+use serde::{Deserialize, Deserializer, Serialize};
+use substrate_primitives::blake2_256;
+use substrate_primitives::storage::StorageKey;
+use tokio::runtime::Runtime;
+
+use crate::modules::base::{get_rpc, Hex};
+use crate::modules::metadata::{
+	self, FunctionArgumentMetadata, StorageMetadata, StorageModifier, StorageType,
+};
+use crate::modules::meter::{get_block_info, BlockInfo, Number};
+use crate::modules::state::{get_block_hash, get_storage_at};
+use crate::modules::{base, Command, Module};
+
+pub fn module<'a, 'b>() -> Module<'a, 'b> {
+	Module {
+		desc: "Runtime tools".to_string(),
+		commands: commands(),
+		get_cases: cases::cases,
+	}
+}
+
+pub fn commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	let mut app = SubCommand::with_name("runtime").about("Runtime tools");
+	for sub_command in sub_commands() {
+		app = app.subcommand(sub_command.app);
+	}
+	let f = run;
+
+	vec![Command { app, f }]
+}
+
+fn run(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	base::run(matches, || sub_commands(), || commands())
+}
+
+fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
+	vec![
+		Command {
+			app: SubCommand::with_name("metadata")
+				.about("Get runtime metadata: modules, calls, events and storage")
+				.arg(rpc_arg())
+				.arg(at_arg())
+				.arg(
+					Arg::with_name("MODULE")
+						.long("module")
+						.short("m")
+						.help("Module name or storage prefix")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FORMAT")
+						.long("format")
+						.short("f")
+						.help("Output format: json for default")
+						.takes_value(true)
+						.possible_values(&["json", "markdown"])
+						.required(false),
+				),
+			f: metadata,
+		},
+		Command {
+			app: SubCommand::with_name("code")
+				.about("Get runtime code (wasm) to a file")
+				.arg(rpc_arg())
+				.arg(at_arg())
+				.arg(
+					Arg::with_name("OUTPUT")
+						.long("out")
+						.short("o")
+						.help("Output wasm file path")
+						.takes_value(true)
+						.required(true),
+				),
+			f: code,
+		},
+		Command {
+			app: SubCommand::with_name("hash")
+				.about("Get blake2_256 hash of runtime code")
+				.arg(rpc_arg())
+				.arg(at_arg()),
+			f: hash,
+		},
+		Command {
+			app: SubCommand::with_name("compare")
+				.about("Compare a wasm file with runtime code before and after the upgrade block")
+				.arg(rpc_arg())
+				.arg(
+					at_arg()
+						.help("Upgrade block number: the block including the set_code call, the code at the block before is the code before the upgrade")
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("FILE")
+						.help("Wasm file path")
+						.required(true)
+						.index(1),
+				),
+			f: compare,
+		},
+		Command {
+			app: SubCommand::with_name("version")
+				.about("Get runtime version")
+				.arg(rpc_arg())
+				.arg(at_arg()),
+			f: version,
+		},
+	]
+}
+
+fn rpc_arg<'a, 'b>() -> Arg<'a, 'b> {
+	Arg::with_name("RPC")
+		.long("rpc")
+		.short("r")
+		.help("RPC address: the profile for default")
+		.takes_value(true)
+		.required(false)
+}
+
+fn at_arg<'a, 'b>() -> Arg<'a, 'b> {
+	Arg::with_name("NUMBER")
+		.long("number")
+		.short("n")
+		.alias("at")
+		.help("Block number: the best block for default")
+		.takes_value(true)
+		.required(false)
+}
+
+#[derive(Serialize)]
+struct SerdeMetadata {
+	version: u8,
+	modules: Vec<SerdeModule>,
+}
+
+#[derive(Serialize)]
+struct SerdeModule {
+	name: String,
+	prefix: String,
+	/// The module index used in call, the same as `module` in `tx compose --call`
+	call_index: Option<u8>,
+	/// The module index used in event
+	event_index: Option<u8>,
+	calls: Vec<SerdeFunction>,
+	events: Vec<SerdeEvent>,
+	storage: Vec<StorageMetadata>,
+}
+
+#[derive(Serialize)]
+struct SerdeFunction {
+	index: u8,
+	name: String,
+	arguments: Vec<FunctionArgumentMetadata>,
+	documentation: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SerdeEvent {
+	index: u8,
+	name: String,
+	arguments: Vec<String>,
+	documentation: Vec<String>,
+}
+
+fn metadata(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Some(number.parse::<u64>().map_err(|_| "Invalid block number")?),
+		None => None,
+	};
+
+	let format = matches.value_of("FORMAT").unwrap_or("json");
+
+	let block_hash = get_block_hash(rpc, number)?;
+	let metadata = metadata::get_metadata(rpc, block_hash)?;
+
+	let modules = get_modules(metadata.modules);
+
+	let modules = match matches.value_of("MODULE") {
+		Some(module) => {
+			let modules = modules
+				.into_iter()
+				.filter(|x| {
+					x.name.eq_ignore_ascii_case(module) || x.prefix.eq_ignore_ascii_case(module)
+				})
+				.collect::<Vec<_>>();
+			if modules.is_empty() {
+				return Err(format!("Module not found: {}", module));
+			}
+			modules
+		}
+		None => modules,
+	};
+
+	let metadata = SerdeMetadata {
+		version: metadata.version,
+		modules,
+	};
+
+	match format {
+		"markdown" => Ok(vec![to_markdown(&metadata)]),
+		_ => base::output(metadata),
+	}
+}
+
+fn code(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let output_path = matches.value_of("OUTPUT").expect("qed");
+
+	let (block_info, code) = get_code(matches, rpc)?;
+
+	base::put_to_file(&code, output_path)?;
+
+	let output = CodeOutput {
+		number: block_info.number,
+		hash: block_info.hash,
+		code_size: code.len(),
+		code_hash: blake2_256(&code).to_vec().into(),
+		output: Some(output_path.to_string()),
+	};
+
+	base::output(output)
+}
+
+fn hash(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let (block_info, code) = get_code(matches, rpc)?;
+
+	let output = CodeOutput {
+		number: block_info.number,
+		hash: block_info.hash,
+		code_size: code.len(),
+		code_hash: blake2_256(&code).to_vec().into(),
+		output: None,
+	};
+
+	base::output(output)
+}
+
+fn compare(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let file = matches.value_of("FILE").expect("qed");
+
+	let file_code = base::get_from_file(file)?;
+
+	let number = matches
+		.value_of("NUMBER")
+		.expect("qed")
+		.parse::<u64>()
+		.map_err(|_| "Invalid block number")?;
+	if number == 0 {
+		return Err("Invalid upgrade block number".to_string());
+	}
+
+	// the code is set in the state after executing the upgrade block
+	let before = get_compare_code(rpc, number - 1)?;
+	let after = get_compare_code(rpc, number)?;
+
+	#[derive(Serialize)]
+	struct Output {
+		/// The code after the upgrade is the same as the file
+		matched: bool,
+		/// The code is changed by the upgrade block
+		changed: bool,
+		before: CompareCode,
+		after: CompareCode,
+		file_size: usize,
+		file_hash: Hex,
+	}
+
+	let file_hash: Hex = blake2_256(&file_code).to_vec().into();
+
+	let output = Output {
+		matched: after.code_hash == file_hash,
+		changed: after.code_hash != before.code_hash,
+		before,
+		after,
+		file_size: file_code.len(),
+		file_hash,
+	};
+
+	base::output(output)
+}
+
+#[derive(Serialize)]
+struct CompareCode {
+	number: u64,
+	hash: Hex,
+	code_size: usize,
+	code_hash: Hex,
+	version: RuntimeVersion,
+}
+
+fn get_compare_code(rpc: &str, number: u64) -> Result<CompareCode, String> {
+	let (block_info, code) = get_code_at(rpc, Number::Number(number))?;
+
+	let block_hash: String = block_info.hash.clone().into();
+	let version = get_runtime_version(rpc, &block_hash)?;
+
+	Ok(CompareCode {
+		number: block_info.number,
+		hash: block_info.hash,
+		code_size: code.len(),
+		code_hash: blake2_256(&code).to_vec().into(),
+		version,
+	})
+}
+
+fn version(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let block_info = get_block_info(get_number(matches)?, rpc)?;
+
+	let block_hash: String = block_info.hash.into();
+	let version = get_runtime_version(rpc, &block_hash)?;
+
+	base::output(version)
+}
+
+#[derive(Serialize)]
+struct CodeOutput {
+	number: u64,
+	hash: Hex,
+	code_size: usize,
+	code_hash: Hex,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	output: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+struct RuntimeVersion {
+	spec_name: String,
+	impl_name: String,
+	authoring_version: u32,
+	spec_version: u32,
+	impl_version: u32,
+	#[serde(deserialize_with = "deserialize_apis")]
+	apis: Vec<RuntimeApi>,
+}
+
+#[derive(Serialize)]
+struct RuntimeApi {
+	id: Hex,
+	name: Option<String>,
+	version: u32,
+}
+
+/// Names of the known runtime api ids: blake2_64 of the api name
+const RUNTIME_API_NAMES: [(&str, &str); 12] = [
+	("0xdf6acb689907609b", "Core"),
+	("0x37e397fc7c91f5e4", "Metadata"),
+	("0x40fe3ad401f8959a", "BlockBuilder"),
+	("0xd2bc9897eed08f15", "TaggedTransactionQueue"),
+	("0xf78b278be53f454c", "OffchainWorkerApi"),
+	("0xed99c5acb25eedf5", "GrandpaApi"),
+	("0xdd718d5cc53262d4", "AuraApi"),
+	("0x6eb83e3f57eeeff6", "ShardingAPI"),
+	("0x1e6525524a4d44ac", "YeePOWApi"),
+	("0x47aa0c87543ebabb", "CrfgApi"),
+	("0x8453b50b22293977", "AssetsApi"),
+	("0x22f09549cb59f9ff", "ConsensusApi"),
+];
+
+fn deserialize_apis<'de, D>(deserializer: D) -> Result<Vec<RuntimeApi>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let apis: Vec<(Hex, u32)> = Deserialize::deserialize(deserializer)?;
+	let apis = apis
+		.into_iter()
+		.map(|(id, version)| {
+			let id_str: String = id.clone().into();
+			let name = RUNTIME_API_NAMES
+				.iter()
+				.find(|(x, _)| x == &id_str)
+				.map(|(_, name)| name.to_string());
+			RuntimeApi { id, name, version }
+		})
+		.collect();
+	Ok(apis)
+}
+
+fn get_number(matches: &ArgMatches) -> Result<Number, String> {
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Number::Number(number.parse::<u64>().map_err(|_| "Invalid block number")?),
+		None => Number::Best,
+	};
+	Ok(number)
+}
+
+fn get_code(matches: &ArgMatches, rpc: &str) -> Result<(BlockInfo, Vec<u8>), String> {
+	get_code_at(rpc, get_number(matches)?)
+}
+
+/// Runtime code in the unhashed storage `:code`
+fn get_code_at(rpc: &str, number: Number) -> Result<(BlockInfo, Vec<u8>), String> {
+	let block_info = get_block_info(number, rpc)?;
+
+	let code = get_storage_at(
+		rpc,
+		StorageKey(b":code".to_vec()),
+		&Some(block_info.hash.clone().into()),
+	)?
+	.ok_or("Runtime code not found")?;
+
+	Ok((block_info, code))
+}
+
+fn get_runtime_version(rpc: &str, block_hash: &str) -> Result<RuntimeVersion, String> {
+	let mut runtime = Runtime::new().expect("qed");
+
+	let version = runtime
+		.block_on(base::rpc_call::<_, RuntimeVersion>(
+			rpc,
+			"state_getRuntimeVersion",
+			&(block_hash,),
+		))?
+		.result
+		.ok_or("Get runtime version failed")?;
+
+	Ok(version)
+}
+
+/// Modules without calls (or events) are skipped when counting call (or event) index
+fn get_modules(modules: Vec<metadata::ModuleMetadata>) -> Vec<SerdeModule> {
+	let mut call_index = 0u8;
+	let mut event_index = 0u8;
+	modules
+		.into_iter()
+		.map(|module| {
+			// a module with a call type takes a call index, even if it has no calls
+			let module_call_index = match module.calls.is_some() {
+				true => {
+					call_index += 1;
+					Some(call_index - 1)
+				}
+				false => None,
+			};
+			let module_event_index = match module.events.is_some() {
+				true => {
+					event_index += 1;
+					Some(event_index - 1)
+				}
+				false => None,
+			};
+			SerdeModule {
+				name: module.name,
+				prefix: module.prefix,
+				call_index: module_call_index,
+				event_index: module_event_index,
+				calls: module
+					.calls
+					.unwrap_or_default()
+					.into_iter()
+					.enumerate()
+					.map(|(index, x)| SerdeFunction {
+						index: index as u8,
+						name: x.name,
+						arguments: x.arguments,
+						documentation: trim_documentation(x.documentation),
+					})
+					.collect(),
+				events: module
+					.events
+					.unwrap_or_default()
+					.into_iter()
+					.enumerate()
+					.map(|(index, x)| SerdeEvent {
+						index: index as u8,
+						name: x.name,
+						arguments: x.arguments,
+						documentation: trim_documentation(x.documentation),
+					})
+					.collect(),
+				storage: module
+					.storage
+					.into_iter()
+					.map(|mut x| {
+						x.documentation = trim_documentation(x.documentation);
+						x
+					})
+					.collect(),
+			}
+		})
+		.collect()
+}
+
+fn trim_documentation(documentation: Vec<String>) -> Vec<String> {
+	documentation
+		.into_iter()
+		.map(|x| x.trim().to_string())
+		.filter(|x| !x.is_empty())
+		.collect()
+}
+
+fn to_markdown(metadata: &SerdeMetadata) -> String {
+	let mut lines = vec![format!("# Runtime metadata (v{})", metadata.version)];
+
+	for module in &metadata.modules {
+		lines.push("".to_string());
+		lines.push(format!("## {}", module.name));
+		lines.push("".to_string());
+		lines.push(format!("- prefix: `{}`", module.prefix));
+		if let Some(index) = module.call_index {
+			lines.push(format!("- call index: {}", index));
+		}
+		if let Some(index) = module.event_index {
+			lines.push(format!("- event index: {}", index));
+		}
+
+		if !module.calls.is_empty() {
+			lines.push("".to_string());
+			lines.push("### Calls".to_string());
+			lines.push("".to_string());
+			lines.push("| index | name | arguments | documentation |".to_string());
+			lines.push("| --- | --- | --- | --- |".to_string());
+			for call in &module.calls {
+				let arguments = call
+					.arguments
+					.iter()
+					.map(|x| format!("{}: `{}`", x.name, x.ty))
+					.collect::<Vec<_>>()
+					.join(", ");
+				lines.push(format!(
+					"| {} | {} | {} | {} |",
+					call.index,
+					call.name,
+					arguments,
+					call.documentation.join(" ")
+				));
+			}
+		}
+
+		if !module.events.is_empty() {
+			lines.push("".to_string());
+			lines.push("### Events".to_string());
+			lines.push("".to_string());
+			lines.push("| index | name | arguments | documentation |".to_string());
+			lines.push("| --- | --- | --- | --- |".to_string());
+			for event in &module.events {
+				let arguments = event
+					.arguments
+					.iter()
+					.map(|x| format!("`{}`", x))
+					.collect::<Vec<_>>()
+					.join(", ");
+				lines.push(format!(
+					"| {} | {} | {} | {} |",
+					event.index,
+					event.name,
+					arguments,
+					event.documentation.join(" ")
+				));
+			}
+		}
+
+		if !module.storage.is_empty() {
+			lines.push("".to_string());
+			lines.push("### Storage".to_string());
+			lines.push("".to_string());
+			lines.push("| name | modifier | type | documentation |".to_string());
+			lines.push("| --- | --- | --- | --- |".to_string());
+			for storage in &module.storage {
+				let modifier = match storage.modifier {
+					StorageModifier::Optional => "Optional",
+					StorageModifier::Default => "Default",
+				};
+				let ty = match &storage.ty {
+					StorageType::Plain { value } => format!("`{}`", value),
+					StorageType::Map { key, value, .. } => format!("`{}` => `{}`", key, value),
+					StorageType::DoubleMap {
+						key1, key2, value, ..
+					} => format!("`{}`, `{}` => `{}`", key1, key2, value),
+				};
+				lines.push(format!(
+					"| {} | {} | {} | {} |",
+					storage.name,
+					modifier,
+					ty,
+					storage.documentation.join(" ")
+				));
+			}
+		}
+	}
+
+	lines.join("\n")
+}
+
+mod cases {
+	use linked_hash_map::LinkedHashMap;
+
+	use crate::modules::Case;
+
+	pub fn cases() -> LinkedHashMap<&'static str, Vec<Case>> {
+		vec![(
+			"runtime",
+			vec![
+				Case {
+					desc: "Get runtime metadata of a module".to_string(),
+					input: vec!["metadata", "-r", "http://localhost:9033", "-m", "Sudo"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![r#"{
+  "result": {
+    "version": 4,
+    "modules": [
+      {
+        "name": "sudo",
+        "prefix": "Sudo",
+        "call_index": 11,
+        "event_index": 9,
+        "calls": [
+          {
+            "index": 0,
+            "name": "sudo",
+            "arguments": [
+              {
+                "name": "proposal",
+                "ty": "Box<T::Proposal>"
+              }
+            ],
+            "documentation": [
+              "Authenticates the sudo key and dispatches a function call with `Root` origin."
+            ]
+          },
+          {
+            "index": 1,
+            "name": "set_key",
+            "arguments": [
+              {
+                "name": "new",
+                "ty": "<T::Lookup as StaticLookup>::Source"
+              }
+            ],
+            "documentation": [
+              "Authenticates the current sudo key and sets the given AccountId (`new`) as the new sudo key."
+            ]
+          }
+        ],
+        "events": [
+          {
+            "index": 0,
+            "name": "Sudid",
+            "arguments": [
+              "bool"
+            ],
+            "documentation": [
+              "A sudo just took place."
+            ]
+          },
+          {
+            "index": 1,
+            "name": "KeyChanged",
+            "arguments": [
+              "AccountId"
+            ],
+            "documentation": [
+              "The sudoer just switched identity; the old key is supplied."
+            ]
+          }
+        ],
+        "storage": [
+          {
+            "name": "Key",
+            "modifier": "Default",
+            "ty": {
+              "Plain": {
+                "value": "T::AccountId"
+              }
+            },
+            "default": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "documentation": [
+              "The `AccountId` of the sudo key."
+            ]
+          }
+        ]
+      }
+    ]
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Get runtime metadata of a module in markdown".to_string(),
+					input: vec![
+						"metadata",
+						"-r",
+						"http://localhost:9033",
+						"-m",
+						"Sudo",
+						"-f",
+						"markdown",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![r#"# Runtime metadata (v4)
+
+## sudo
+
+- prefix: `Sudo`
+- call index: 11
+- event index: 9
+
+### Calls
+
+| index | name | arguments | documentation |
+| --- | --- | --- | --- |
+| 0 | sudo | proposal: `Box<T::Proposal>` | Authenticates the sudo key and dispatches a function call with `Root` origin. |
+| 1 | set_key | new: `<T::Lookup as StaticLookup>::Source` | Authenticates the current sudo key and sets the given AccountId (`new`) as the new sudo key. |
+
+### Events
+
+| index | name | arguments | documentation |
+| --- | --- | --- | --- |
+| 0 | Sudid | `bool` | A sudo just took place. |
+| 1 | KeyChanged | `AccountId` | The sudoer just switched identity; the old key is supplied. |
+
+### Storage
+
+| name | modifier | type | documentation |
+| --- | --- | --- | --- |
+| Key | Default | `T::AccountId` | The `AccountId` of the sudo key. |"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Get runtime code to a file".to_string(),
+					input: vec!["code", "-r", "http://localhost:9033", "--at", "100", "-o", "runtime.wasm"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![r#"{
+  "result": {
+    "number": 100,
+    "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+    "code_size": 621893,
+    "code_hash": "0x9c5b0a6f3e1d2c4b8a7f6e5d4c3b2a190817263544536271809fa0b1c2d3e4f5",
+    "output": "runtime.wasm"
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Get runtime code hash".to_string(),
+					input: vec!["hash", "-r", "http://localhost:9033"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![r#"{
+  "result": {
+    "number": 100,
+    "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+    "code_size": 621893,
+    "code_hash": "0x9c5b0a6f3e1d2c4b8a7f6e5d4c3b2a190817263544536271809fa0b1c2d3e4f5"
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Compare wasm file with runtime code before and after the upgrade block"
+						.to_string(),
+					input: vec![
+						"compare",
+						"-r",
+						"http://localhost:9033",
+						"--at",
+						"100",
+						"yee_runtime.compact.wasm",
+					]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					output: vec![r#"{
+  "result": {
+    "matched": true,
+    "changed": true,
+    "before": {
+      "number": 99,
+      "hash": "0x5e0c7a4b3f2d1e6a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a",
+      "code_size": 618421,
+      "code_hash": "0x4a3b2c1d0e9f8a7b6c5d4e3f2a1b0c9d8e7f6a5e0c7a4b3f2d1e6a9b8c7d6e5f",
+      "version": {
+        "spec_name": "yee",
+        "impl_name": "yee-rs",
+        "authoring_version": 3,
+        "spec_version": 3,
+        "impl_version": 3,
+        "apis": [
+          {
+            "id": "0xdf6acb689907609b",
+            "name": "Core",
+            "version": 2
+          },
+          {
+            "id": "0x37e397fc7c91f5e4",
+            "name": "Metadata",
+            "version": 1
+          },
+          {
+            "id": "0x40fe3ad401f8959a",
+            "name": "BlockBuilder",
+            "version": 3
+          }
+        ]
+      }
+    },
+    "after": {
+      "number": 100,
+      "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+      "code_size": 621893,
+      "code_hash": "0x9c5b0a6f3e1d2c4b8a7f6e5d4c3b2a190817263544536271809fa0b1c2d3e4f5",
+      "version": {
+        "spec_name": "yee",
+        "impl_name": "yee-rs",
+        "authoring_version": 3,
+        "spec_version": 4,
+        "impl_version": 4,
+        "apis": [
+          {
+            "id": "0xdf6acb689907609b",
+            "name": "Core",
+            "version": 2
+          },
+          {
+            "id": "0x37e397fc7c91f5e4",
+            "name": "Metadata",
+            "version": 1
+          },
+          {
+            "id": "0x40fe3ad401f8959a",
+            "name": "BlockBuilder",
+            "version": 3
+          }
+        ]
+      }
+    },
+    "file_size": 621893,
+    "file_hash": "0x9c5b0a6f3e1d2c4b8a7f6e5d4c3b2a190817263544536271809fa0b1c2d3e4f5"
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+				Case {
+					desc: "Get runtime version".to_string(),
+					input: vec!["version", "-r", "http://localhost:9033"]
+						.into_iter()
+						.map(Into::into)
+						.collect(),
+					output: vec![r#"{
+  "result": {
+    "spec_name": "yee",
+    "impl_name": "yee-rs",
+    "authoring_version": 3,
+    "spec_version": 4,
+    "impl_version": 4,
+    "apis": [
+      {
+        "id": "0xdf6acb689907609b",
+        "name": "Core",
+        "version": 2
+      },
+      {
+        "id": "0x37e397fc7c91f5e4",
+        "name": "Metadata",
+        "version": 1
+      },
+      {
+        "id": "0x40fe3ad401f8959a",
+        "name": "BlockBuilder",
+        "version": 3
+      }
+    ]
+  }
+}"#]
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+					is_example: true,
+					is_test: false,
+					since: "0.14.0".to_string(),
+				},
+			],
+		)]
+		.into_iter()
+		.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::modules::base::test::test_module;
+
+	use super::*;
+
+	#[test]
+	fn test_cases() {
+		test_module(module());
+	}
+
+	#[test]
+	fn test_runtime_api_names() {
+		for (id, name) in RUNTIME_API_NAMES.iter() {
+			let hash = blake2_rfc::blake2b::blake2b(8, &[], name.as_bytes());
+			let hash = format!("0x{}", hex::encode(hash.as_bytes()));
+			assert_eq!(*id, hash, "{}", name);
+		}
+	}
+
+	#[test]
+	fn test_get_modules_index() {
+		let module = |name: &str, has_call: bool, has_event: bool| metadata::ModuleMetadata {
+			name: name.to_string(),
+			prefix: name.to_string(),
+			storage: vec![],
+			calls: if has_call { Some(vec![]) } else { None },
+			events: if has_event { Some(vec![]) } else { None },
+		};
+		let modules = get_modules(vec![
+			module("system", false, true),
+			module("timestamp", true, false),
+			module("balances", true, true),
+		]);
+		let call_index_list = modules.iter().map(|x| x.call_index).collect::<Vec<_>>();
+		assert_eq!(call_index_list, vec![None, Some(0), Some(1)]);
+		let event_index_list = modules.iter().map(|x| x.event_index).collect::<Vec<_>>();
+		assert_eq!(event_index_list, vec![Some(0), None, Some(1)]);
+	}
+}
@@ -1,15 +1,27 @@
+use std::cmp::min;
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 
 use clap::{Arg, ArgMatches, SubCommand};
-use parity_codec::{Codec, KeyedVec};
-use substrate_primitives::blake2_256;
+use parity_codec::{Codec, Compact, Encode, KeyedVec};
+use prettytable::{format, Cell, Row, Table};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use substrate_primitives::storage::{StorageData, StorageKey};
+use substrate_primitives::{blake2_256, Blake2Hasher, H256};
+use substrate_state_machine::read_proof_check;
 use tokio::runtime::Runtime;
 use yee_primitives::Address;
 use yee_primitives::AddressCodec;
+use yee_primitives::Hrp;
+use yee_sharding_primitives::utils::shard_num_for_bytes;
 
-use crate::modules::base::{get_rpc, Hex};
-use crate::modules::{base, Command, Module};
+use crate::modules::account::parse_public_key;
+use crate::modules::base::{get_rpc, Hex, METHOD_NOT_FOUND};
+use crate::modules::event::get_block_events;
+use crate::modules::meter::{get_block_info, is_mainnet, Number};
+use crate::modules::tx::{get_account_extrinsics, AccountExtrinsic};
+use crate::modules::{base, metadata, Command, Module};
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
 	Module {
@@ -42,9 +54,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("NUMBER")
@@ -69,9 +81,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("NUMBER")
@@ -96,9 +108,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("NUMBER")
@@ -129,9 +141,19 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default, the addresses of a report should be in the shard of the rpc")
 						.takes_value(true)
-						.required(true),
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the shard of the account")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("NUMBER")
@@ -141,10 +163,27 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.takes_value(true)
 						.required(false),
 				)
+				.arg(
+					Arg::with_name("FILE")
+						.long("file")
+						.help("File of addresses, one per line")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FORMAT")
+						.long("format")
+						.short("f")
+						.help("Output format of the report with reserved balance, nonce and total issuance")
+						.takes_value(true)
+						.possible_values(&["json", "table", "csv"])
+						.required(false),
+				)
 				.arg(
 					Arg::with_name("ADDRESS")
-						.help("Address")
-						.required(true)
+						.help("Address: a single address without format outputs the free balance only")
+						.multiple(true)
+						.required_unless("FILE")
 						.index(1),
 				),
 			f: balance,
@@ -156,9 +195,19 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the shard of the account")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("NUMBER")
@@ -176,11 +225,289 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				),
 			f: nonce,
 		},
+		Command {
+			app: SubCommand::with_name("get")
+				.about("Get storage decoded by runtime metadata")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.help("Block number")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MODULE")
+						.help("Module name or storage prefix, e.g. balances")
+						.required(true)
+						.index(1),
+				)
+				.arg(
+					Arg::with_name("ITEM")
+						.help("Storage item name, e.g. FreeBalance")
+						.required(true)
+						.index(2),
+				)
+				.arg(
+					Arg::with_name("KEY")
+						.help("Map key: address, number or hex of the encoded key")
+						.required(false)
+						.index(3),
+				)
+				.arg(
+					Arg::with_name("KEY2")
+						.help("Double map key2: address, number or hex of the encoded key")
+						.required(false)
+						.index(4),
+				),
+			f: get,
+		},
+		Command {
+			app: SubCommand::with_name("keys")
+				.about("Get storage keys under a prefix")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.help("Block number")
+						.takes_value(true)
+						.required(false),
+				)
+				.args(&key_args(true))
+				.arg(
+					Arg::with_name("START_KEY")
+						.long("start-key")
+						.help("Page: return keys after the start key, all keys under the prefix are still fetched from the node for every page (substrate 1.0 has no state_getKeysPaged)")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("COUNT")
+						.long("count")
+						.short("c")
+						.help("Page: key count, 100 for default, only limits the output, not the keys fetched")
+						.takes_value(true)
+						.required(false),
+				),
+			f: keys,
+		},
+		Command {
+			app: SubCommand::with_name("dump")
+				.about("Dump storage key/value pairs under a prefix to a json file")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.alias("at")
+						.help("Block number: the best block for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.args(&key_args(true))
+				.arg(
+					Arg::with_name("OUTPUT")
+						.long("output")
+						.short("o")
+						.help("Output json file path")
+						.takes_value(true)
+						.required(true),
+				),
+			f: dump,
+		},
+		Command {
+			app: SubCommand::with_name("diff")
+				.about("Diff storage between two blocks")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FROM")
+						.long("from")
+						.help("From block number")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("TO")
+						.long("to")
+						.help("To block number: the best block for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.args(&key_args(false))
+				.arg(
+					Arg::with_name("KEYS_FILE")
+						.long("keys")
+						.short("k")
+						.help("File of storage keys in hex, one per line")
+						.takes_value(true)
+						.required(false),
+				),
+			f: diff,
+		},
+		Command {
+			app: SubCommand::with_name("balance_history")
+				.about("Get balance history: blocks where the balance changed")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the shard of the account")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FROM")
+						.long("from")
+						.help("From block number")
+						.takes_value(true)
+						.required(true),
+				)
+				.arg(
+					Arg::with_name("TO")
+						.long("to")
+						.help("To block number: the best block for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("STEP")
+						.long("step")
+						.short("s")
+						.help("Max span to bisect (default: 100): spans with the same balance at both ends are skipped, so changes reverted within a span (e.g. A -> B -> A) are not found; use a smaller step to find them")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("ADDRESS")
+						.help("Address")
+						.required(true)
+						.index(1),
+				),
+			f: balance_history,
+		},
+		Command {
+			app: SubCommand::with_name("proof")
+				.about("Get storage read proof bundle which can be verified offline")
+				.arg(
+					Arg::with_name("RPC")
+						.long("rpc")
+						.short("r")
+						.help("RPC address: the profile for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NUMBER")
+						.long("number")
+						.short("n")
+						.alias("at")
+						.help("Block number: the best block for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("OUTPUT")
+						.long("output")
+						.short("o")
+						.help("Output json file path of the bundle")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KEY")
+						.help("Storage key: hex")
+						.required(true)
+						.index(1),
+				),
+			f: proof,
+		},
+		Command {
+			app: SubCommand::with_name("verify_proof")
+				.about("Verify storage read proof bundle offline")
+				.arg(
+					Arg::with_name("HASH")
+						.long("hash")
+						.help("Trusted block hash, e.g. from a trusted node: the header of the bundle is trusted only if matched")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FILE")
+						.help("Json file path of the bundle")
+						.required(true)
+						.index(1),
+				),
+			f: verify_proof,
+		},
+	]
+}
+
+fn key_args<'a, 'b>(prefix_required: bool) -> Vec<Arg<'a, 'b>> {
+	vec![
+		Arg::with_name("PREFIX")
+			.long("prefix")
+			.short("p")
+			.help("Key prefix: hex, or module and storage name, e.g. \"Balances FreeBalance\"; map keys are hashed without the prefix, so a map is listed by the known accounts only, full enumeration is not supported")
+			.takes_value(true)
+			.required(prefix_required),
+		Arg::with_name("ACCOUNT")
+			.long("account")
+			.short("a")
+			.help("Known account to get the hashed map key: address or public key")
+			.takes_value(true)
+			.multiple(true)
+			.number_of_values(1)
+			.required(false),
+		Arg::with_name("ACCOUNTS_FILE")
+			.long("accounts-file")
+			.help("File of known accounts to get the hashed map keys, one per line")
+			.takes_value(true)
+			.required(false),
 	]
 }
 
 fn value(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 	let key = matches.value_of("KEY").expect("qed");
 
 	let key = key.as_bytes();
@@ -200,7 +527,7 @@ fn value(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn unhashed_value(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 	let key = matches.value_of("KEY").expect("qed");
 
 	let key = key.as_bytes();
@@ -220,7 +547,7 @@ fn unhashed_value(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn map(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 
 	let prefix = matches.value_of("PREFIX").expect("qed");
 	let key = matches.value_of("KEY").expect("qed");
@@ -243,43 +570,63 @@ fn map(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn balance(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
-
-	let prefix = "Balances FreeBalance";
-	let address = matches.value_of("ADDRESS").expect("qed");
-	let address = Address(address.to_string());
-	let (public_key, _hrp) =
-		<[u8; 32]>::from_address(&address).map_err(|_| "Address decode failed")?;
-
-	let prefix = prefix.as_bytes().to_vec();
-	let key: Vec<u8> = public_key.to_vec();
+	let mut addresses = match matches.values_of("ADDRESS") {
+		Some(values) => values.map(str::to_string).collect::<Vec<_>>(),
+		None => vec![],
+	};
+	if let Some(file) = matches.value_of("FILE") {
+		let content = base::get_from_file(file)?;
+		let content = String::from_utf8(content).map_err(|_| "Invalid address file")?;
+		for line in content.lines().map(str::trim).filter(|x| !x.is_empty()) {
+			addresses.push(line.to_string());
+		}
+	}
 
 	let number = match matches.value_of("NUMBER") {
 		Some(number) => Some(number.parse::<u64>().map_err(|_| "Invalid block number")?),
 		None => None,
 	};
 
-	let storage_key = get_map_storage_key(&key, &prefix);
+	let format = matches.value_of("FORMAT");
 
-	let data = get_storage(rpc, storage_key, number)?;
+	if format.is_none() && addresses.len() == 1 && matches.value_of("FILE").is_none() {
+		let (public_key, _hrp) = <[u8; 32]>::from_address(&Address(addresses[0].clone()))
+			.map_err(|_| "Address decode failed")?;
 
-	let data: Option<u128> = match data {
-		Some(data) => Some(u128_from_slice(&data)?),
-		None => None,
-	};
+		let rpc = &base::get_rpc_by_public_key(matches, &public_key)?;
 
-	base::output(&data)
+		let data = get_storage(
+			rpc,
+			get_map_storage_key(&public_key, b"Balances FreeBalance"),
+			number,
+		)?;
+
+		let data: Option<u128> = match data {
+			Some(data) => Some(u128_from_slice(&data)?),
+			None => None,
+		};
+
+		return base::output(&data);
+	}
+
+	let report = get_balance_report(matches, &addresses, number)?;
+
+	match format {
+		Some("table") => Ok(report.to_table()),
+		Some("csv") => Ok(report.to_csv()),
+		_ => base::output(report),
+	}
 }
 
 fn nonce(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
-
 	let prefix = "System AccountNonce";
 	let address = matches.value_of("ADDRESS").expect("qed");
 	let address = Address(address.to_string());
 	let (public_key, _hrp) =
 		<[u8; 32]>::from_address(&address).map_err(|_| "Address decode failed")?;
 
+	let rpc = &base::get_rpc_by_public_key(matches, &public_key)?;
+
 	let prefix = prefix.as_bytes().to_vec();
 	let key: Vec<u8> = public_key.to_vec();
 
@@ -300,35 +647,1195 @@ fn nonce(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	base::output(&data)
 }
 
-fn u128_from_slice(bytes: &[u8]) -> Result<u128, String> {
-	const LEN: usize = 16;
-	if bytes.len() != LEN {
-		return Err("u128 decode error".to_string());
+fn get(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let module = matches.value_of("MODULE").expect("qed");
+	let item = matches.value_of("ITEM").expect("qed");
+
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Some(number.parse::<u64>().map_err(|_| "Invalid block number")?),
+		None => None,
+	};
+
+	let block_hash = get_block_hash(rpc, number)?;
+	let metadata = metadata::get_metadata(rpc, block_hash)?;
+
+	let (module, storage) = metadata.storage(module, item)?;
+
+	let key_types = storage.key_types();
+	let inputs = vec![matches.value_of("KEY"), matches.value_of("KEY2")]
+		.into_iter()
+		.flatten()
+		.collect::<Vec<_>>();
+	if inputs.len() != key_types.len() {
+		return Err(format!(
+			"{} {} requires {} key(s): {}",
+			module.name,
+			storage.name,
+			key_types.len(),
+			key_types.join(", ")
+		));
 	}
-	let mut array = [0; LEN];
-	let bytes = &bytes[..];
-	array.copy_from_slice(bytes);
-	Ok(u128::from_le_bytes(array))
-}
+	let keys = key_types
+		.iter()
+		.zip(inputs)
+		.map(|(ty, input)| metadata::Type::parse(ty).encode(input))
+		.collect::<Result<Vec<_>, _>>()?;
 
-fn u64_from_slice(bytes: &[u8]) -> Result<u64, String> {
-	const LEN: usize = 8;
-	if bytes.len() != LEN {
-		return Err("u128 decode error".to_string());
+	let storage_key = storage.storage_key(&module.prefix, &keys)?;
+
+	let data = get_storage(rpc, StorageKey(storage_key.clone()), number)?;
+
+	let data = match (data, &storage.modifier) {
+		(Some(data), _) => Some(data),
+		(None, metadata::StorageModifier::Default) => Some(storage.default.clone().into()),
+		(None, metadata::StorageModifier::Optional) => None,
+	};
+
+	let value = match &data {
+		Some(data) => {
+			let mainnet = is_mainnet(rpc)?;
+			let value = metadata::Type::parse(storage.value_type())
+				.decode(&mut &data[..], mainnet)
+				.map_err(|e| format!("{}: {}", storage.value_type(), e))?;
+			Some(value)
+		}
+		None => None,
+	};
+
+	#[derive(Serialize)]
+	struct Output {
+		module: String,
+		item: String,
+		#[serde(rename = "type")]
+		ty: String,
+		modifier: metadata::StorageModifier,
+		key: Hex,
+		raw: Option<Hex>,
+		value: Option<Value>,
 	}
-	let mut array = [0; LEN];
-	let bytes = &bytes[..];
-	array.copy_from_slice(bytes);
-	Ok(u64::from_le_bytes(array))
-}
 
-pub fn get_value_storage_key(key: &[u8]) -> StorageKey {
-	StorageKey(twox_128(key))
+	let output = Output {
+		module: module.name.clone(),
+		item: storage.name.clone(),
+		ty: storage.value_type().to_string(),
+		modifier: storage.modifier.clone(),
+		key: storage_key.into(),
+		raw: data.map(Into::into),
+		value,
+	};
+
+	base::output(output)
 }
 
-pub fn get_map_storage_key(key: &[u8], prefix: &[u8]) -> StorageKey {
-	let mut prefix = prefix.to_vec();
-	prefix.extend(key);
+fn keys(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Some(number.parse::<u64>().map_err(|_| "Invalid block number")?),
+		None => None,
+	};
+
+	let start_key: Option<Vec<u8>> = match matches.value_of("START_KEY") {
+		Some(start_key) => Some(Hex::from_str(start_key)?.into()),
+		None => None,
+	};
+
+	let count = match matches.value_of("COUNT") {
+		Some(count) => count.parse::<usize>().map_err(|_| "Invalid count")?,
+		None => 100,
+	};
+	if count == 0 {
+		return Err("Invalid count".to_string());
+	}
+
+	let block_hash = get_block_hash(rpc, number)?;
+
+	// page on the full key list, substrate 1.0 has no state_getKeysPaged
+	let (_storage, items) = get_key_items(matches, rpc, &[&block_hash])?;
+
+	let items = items
+		.into_iter()
+		.filter(|x| match &start_key {
+			Some(start_key) => &x.key > start_key,
+			None => true,
+		})
+		.collect::<Vec<_>>();
+
+	let next_start_key = match items.len() > count {
+		true => Some(items[count - 1].key.clone()),
+		false => None,
+	};
+
+	let keys = items
+		.into_iter()
+		.take(count)
+		.map(|x| x.into())
+		.collect::<Vec<SerdeKeyItem>>();
+
+	#[derive(Serialize)]
+	struct Output {
+		keys: Vec<SerdeKeyItem>,
+		next_start_key: Option<Hex>,
+	}
+
+	let output = Output {
+		keys,
+		next_start_key: next_start_key.map(Into::into),
+	};
+
+	base::output(output)
+}
+
+fn dump(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Number::Number(number.parse::<u64>().map_err(|_| "Invalid block number")?),
+		None => Number::Best,
+	};
+
+	let output_path = matches.value_of("OUTPUT").expect("qed");
+
+	let block_info = get_block_info(number, rpc)?;
+	let block_hash = Some(block_info.hash.clone().into());
+
+	let (storage, items) = get_key_items(matches, rpc, &[&block_hash])?;
+
+	let value_type = storage.map(|x| metadata::Type::parse(x.value_type()));
+	let mainnet = is_mainnet(rpc)?;
+
+	#[derive(Serialize)]
+	struct Entry {
+		key: Hex,
+		account: Option<String>,
+		raw: Option<Hex>,
+		value: Option<Value>,
+	}
+
+	let entries = items
+		.into_iter()
+		.map(|item| {
+			let data = get_storage_at(rpc, StorageKey(item.key.clone()), &block_hash)?;
+			let value = match (&value_type, &data) {
+				(Some(value_type), Some(data)) => {
+					let value = value_type
+						.decode(&mut &data[..], mainnet)
+						.map_err(|e| format!("0x{}: {}", hex::encode(&item.key), e))?;
+					Some(value)
+				}
+				_ => None,
+			};
+			Ok(Entry {
+				key: item.key.into(),
+				account: item.account,
+				raw: data.map(Into::into),
+				value,
+			})
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	#[derive(Serialize)]
+	struct Dump {
+		number: u64,
+		hash: Hex,
+		prefix: String,
+		entries: Vec<Entry>,
+	}
+
+	let count = entries.len();
+
+	let dump = Dump {
+		number: block_info.number,
+		hash: block_info.hash,
+		prefix: matches.value_of("PREFIX").expect("qed").to_string(),
+		entries,
+	};
+
+	let content = serde_json::to_string_pretty(&dump).map_err(|_| "Json encode failed")?;
+	base::put_to_file(content.as_bytes(), output_path)?;
+
+	#[derive(Serialize)]
+	struct Output {
+		number: u64,
+		hash: Hex,
+		count: usize,
+		output: String,
+	}
+
+	let output = Output {
+		number: dump.number,
+		hash: dump.hash,
+		count,
+		output: output_path.to_string(),
+	};
+
+	base::output(output)
+}
+
+fn diff(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let from = matches
+		.value_of("FROM")
+		.expect("qed")
+		.parse::<u64>()
+		.map_err(|_| "Invalid from block number")?;
+
+	let to = match matches.value_of("TO") {
+		Some(to) => Number::Number(to.parse::<u64>().map_err(|_| "Invalid to block number")?),
+		None => Number::Best,
+	};
+
+	if matches.value_of("PREFIX").is_none() && matches.value_of("KEYS_FILE").is_none() {
+		return Err("Either --prefix or --keys is required".to_string());
+	}
+
+	let from = get_block_info(Number::Number(from), rpc)?;
+	let to = get_block_info(to, rpc)?;
+	let from_hash = Some(from.hash.clone().into());
+	let to_hash = Some(to.hash.clone().into());
+
+	// keys existing in either block
+	let mut keys = BTreeMap::new();
+	let mut storage = None;
+	if matches.value_of("PREFIX").is_some() {
+		let (prefix_storage, items) = get_key_items(matches, rpc, &[&from_hash, &to_hash])?;
+		for item in items {
+			keys.insert(item.key, item.account);
+		}
+		storage = prefix_storage;
+	}
+	if let Some(keys_file) = matches.value_of("KEYS_FILE") {
+		let content = base::get_from_file(keys_file)?;
+		let content = String::from_utf8(content).map_err(|_| "Invalid keys file")?;
+		for line in content.lines().map(str::trim).filter(|x| !x.is_empty()) {
+			let key: Vec<u8> = Hex::from_str(line)?.into();
+			keys.entry(key).or_insert(None);
+		}
+	}
+
+	let value_type = storage
+		.as_ref()
+		.map(|x| metadata::Type::parse(x.value_type()));
+	let default = storage.as_ref().and_then(|x| match x.modifier {
+		metadata::StorageModifier::Default => Some(x.default.clone().into()),
+		metadata::StorageModifier::Optional => None,
+	});
+	let mainnet = is_mainnet(rpc)?;
+
+	let decode = |key: &[u8], data: &Option<Vec<u8>>| -> Result<Option<Value>, String> {
+		match (&value_type, data) {
+			(Some(value_type), Some(data)) => value_type
+				.decode(&mut &data[..], mainnet)
+				.map(Some)
+				.map_err(|e| format!("0x{}: {}", hex::encode(key), e)),
+			_ => Ok(None),
+		}
+	};
+
+	#[derive(Serialize)]
+	struct Entry {
+		key: Hex,
+		account: Option<String>,
+		old_raw: Option<Hex>,
+		new_raw: Option<Hex>,
+		old: Option<Value>,
+		new: Option<Value>,
+		delta: Option<String>,
+	}
+
+	let mut added = vec![];
+	let mut removed = vec![];
+	let mut changed = vec![];
+
+	for (key, account) in keys {
+		let old_data = get_storage_at(rpc, StorageKey(key.clone()), &from_hash)?;
+		let new_data = get_storage_at(rpc, StorageKey(key.clone()), &to_hash)?;
+		if old_data == new_data {
+			continue;
+		}
+
+		// absent value is the default value for the delta
+		let delta = match &value_type {
+			Some(value_type) => {
+				let old = old_data.clone().or_else(|| default.clone());
+				let new = new_data.clone().or_else(|| default.clone());
+				match (
+					old.and_then(|x| value_type.decode_integer(&x)),
+					new.and_then(|x| value_type.decode_integer(&x)),
+				) {
+					(Some(old), Some(new)) if new >= old => Some(format!("+{}", new - old)),
+					(Some(old), Some(new)) => Some(format!("-{}", old - new)),
+					_ => None,
+				}
+			}
+			None => None,
+		};
+
+		let entry = Entry {
+			old: decode(&key, &old_data)?,
+			new: decode(&key, &new_data)?,
+			key: key.into(),
+			account,
+			old_raw: old_data.clone().map(Into::into),
+			new_raw: new_data.clone().map(Into::into),
+			delta,
+		};
+
+		match (old_data, new_data) {
+			(None, Some(_)) => added.push(entry),
+			(Some(_), None) => removed.push(entry),
+			_ => changed.push(entry),
+		}
+	}
+
+	#[derive(Serialize)]
+	struct Block {
+		number: u64,
+		hash: Hex,
+	}
+
+	#[derive(Serialize)]
+	struct Output {
+		from: Block,
+		to: Block,
+		added: Vec<Entry>,
+		removed: Vec<Entry>,
+		changed: Vec<Entry>,
+	}
+
+	let output = Output {
+		from: Block {
+			number: from.number,
+			hash: from.hash,
+		},
+		to: Block {
+			number: to.number,
+			hash: to.hash,
+		},
+		added,
+		removed,
+		changed,
+	};
+
+	base::output(output)
+}
+
+/// Default max span to bisect in balance history
+const DEFAULT_BALANCE_HISTORY_STEP: u64 = 100;
+
+fn balance_history(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let address = matches.value_of("ADDRESS").expect("qed");
+	let (public_key, _hrp) = <[u8; 32]>::from_address(&Address(address.to_string()))
+		.map_err(|_| "Address decode failed")?;
+
+	let rpc = &base::get_rpc_by_public_key(matches, &public_key)?;
+
+	let from = matches
+		.value_of("FROM")
+		.expect("qed")
+		.parse::<u64>()
+		.map_err(|_| "Invalid from block number")?;
+
+	let to = match matches.value_of("TO") {
+		Some(to) => to.parse::<u64>().map_err(|_| "Invalid to block number")?,
+		None => get_block_info(Number::Best, rpc)?.number,
+	};
+
+	if from > to {
+		return Err("Invalid block range".to_string());
+	}
+
+	let step = match matches.value_of("STEP") {
+		Some(step) => step.parse::<u64>().map_err(|_| "Invalid step")?,
+		None => DEFAULT_BALANCE_HISTORY_STEP,
+	};
+	if step == 0 && from < to {
+		return Err("Invalid step".to_string());
+	}
+
+	let storage_key = get_map_storage_key(&public_key, b"Balances FreeBalance");
+
+	let mut balances = BTreeMap::new();
+	let mut changes = vec![];
+	{
+		let mut balance_at = |number| balance_at(rpc, &storage_key, number, &mut balances);
+		let mut start = from;
+		while start < to {
+			let end = min(start + step, to);
+			find_balance_changes(start, end, &mut balance_at, &mut changes)?;
+			start = end;
+		}
+	}
+
+	let initial_balance = balance_at(rpc, &storage_key, from, &mut balances)?;
+
+	#[derive(Serialize)]
+	struct Row {
+		number: u64,
+		hash: Hex,
+		timestamp: Option<u64>,
+		balance: u128,
+		delta: String,
+		extrinsics: Vec<AccountExtrinsic>,
+		events: Vec<String>,
+	}
+
+	let public_key_hex = hex::encode(&public_key);
+
+	let rows = changes
+		.into_iter()
+		.map(|number| {
+			let block_info = get_block_info(Number::Number(number), rpc)?;
+			let block_hash: Vec<u8> = block_info.hash.clone().into();
+			let balance = balances[&number];
+			let previous = balances[&(number - 1)];
+			let delta = match balance >= previous {
+				true => format!("+{}", balance - previous),
+				false => format!("-{}", previous - balance),
+			};
+			let extrinsics = get_account_extrinsics(rpc, &block_hash, &public_key)?;
+			let events = get_block_events(rpc, &block_hash)?
+				.into_iter()
+				.filter(|x| x.contains(&public_key_hex))
+				.collect();
+			Ok(Row {
+				number,
+				hash: block_info.hash,
+				timestamp: block_info.pow.map(|x| x.timestamp),
+				balance,
+				delta,
+				extrinsics,
+				events,
+			})
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	#[derive(Serialize)]
+	struct Output {
+		address: String,
+		from: u64,
+		to: u64,
+		initial_balance: u128,
+		history: Vec<Row>,
+	}
+
+	let output = Output {
+		address: address.to_string(),
+		from,
+		to,
+		initial_balance,
+		history: rows,
+	};
+
+	base::output(output)
+}
+
+/// Bisect the span, skip it if the balance is the same at both ends
+fn find_balance_changes<F>(
+	from: u64,
+	to: u64,
+	balance_at: &mut F,
+	changes: &mut Vec<u64>,
+) -> Result<(), String>
+where
+	F: FnMut(u64) -> Result<u128, String>,
+{
+	let from_balance = balance_at(from)?;
+	let to_balance = balance_at(to)?;
+	if from_balance == to_balance {
+		return Ok(());
+	}
+	if to == from + 1 {
+		changes.push(to);
+		return Ok(());
+	}
+	let middle = from + (to - from) / 2;
+	find_balance_changes(from, middle, balance_at, changes)?;
+	find_balance_changes(middle, to, balance_at, changes)?;
+	Ok(())
+}
+
+fn balance_at(
+	rpc: &str,
+	storage_key: &StorageKey,
+	number: u64,
+	balances: &mut BTreeMap<u64, u128>,
+) -> Result<u128, String> {
+	if let Some(balance) = balances.get(&number) {
+		return Ok(*balance);
+	}
+	let balance = match get_storage(rpc, storage_key.clone(), Some(number))? {
+		Some(data) => u128_from_slice(&data)?,
+		None => 0,
+	};
+	balances.insert(number, balance);
+	Ok(balance)
+}
+
+fn proof(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let rpc = &get_rpc(matches)?;
+
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Number::Number(number.parse::<u64>().map_err(|_| "Invalid block number")?),
+		None => Number::Best,
+	};
+
+	let key: Vec<u8> = Hex::from_str(matches.value_of("KEY").expect("qed"))?.into();
+
+	let block_hash: String = get_block_info(number, rpc)?.hash.into();
+
+	let header = get_proof_header(rpc, &block_hash)?;
+
+	let mut runtime = Runtime::new().expect("qed");
+
+	#[derive(Deserialize)]
+	struct ReadProof {
+		proof: Vec<Hex>,
+	}
+
+	let result = runtime.block_on(base::rpc_call::<_, ReadProof>(
+		rpc,
+		"state_getReadProof",
+		&(vec![StorageKey(key.clone())], &block_hash),
+	))?;
+
+	if let Some(error) = result.error {
+		// the node may not support read proof
+		if error.code == METHOD_NOT_FOUND {
+			return Err(
+				"Get read proof failed: state_getReadProof not supported by the node".to_string(),
+			);
+		}
+		return Err(format!("Get read proof failed: {}", error.message));
+	}
+
+	let proof = result.result.ok_or("Get read proof failed")?.proof;
+
+	let value = get_storage_at(rpc, StorageKey(key.clone()), &Some(block_hash))?;
+
+	let bundle = ProofBundle {
+		header,
+		key: key.into(),
+		value: value.map(Into::into),
+		proof,
+	};
+
+	match matches.value_of("OUTPUT") {
+		Some(output_path) => {
+			let content =
+				serde_json::to_string_pretty(&bundle).map_err(|_| "Json encode failed")?;
+			base::put_to_file(content.as_bytes(), output_path)?;
+
+			#[derive(Serialize)]
+			struct Output {
+				number: u64,
+				hash: Hex,
+				output: String,
+			}
+
+			let output = Output {
+				number: bundle.header.number,
+				hash: bundle.header.hash,
+				output: output_path.to_string(),
+			};
+			base::output(output)
+		}
+		None => base::output(bundle),
+	}
+}
+
+fn verify_proof(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let file = matches.value_of("FILE").expect("qed");
+
+	let content = base::get_from_file(file)?;
+	let bundle: ProofBundle =
+		serde_json::from_slice(&content).map_err(|e| format!("Invalid bundle: {}", e))?;
+
+	let trusted_hash: Option<Vec<u8>> = match matches.value_of("HASH") {
+		Some(hash) => Some(Hex::from_str(hash)?.into()),
+		None => None,
+	};
+
+	let header_hash = bundle.header.compute_hash();
+	let expected_header_hash: Vec<u8> = bundle.header.hash.clone().into();
+	let header_valid = header_hash == expected_header_hash;
+
+	// the bundle is self-consistent only, unless the header matches the trusted block hash
+	let trusted = trusted_hash.map(|x| x == header_hash).unwrap_or(false);
+
+	let state_root: Vec<u8> = bundle.header.state_root.clone().into();
+	if state_root.len() != 32 {
+		return Err("Invalid state root".to_string());
+	}
+	let key: Vec<u8> = bundle.key.clone().into();
+	let proof = bundle
+		.proof
+		.iter()
+		.map(|x| x.clone().into())
+		.collect::<Vec<Vec<u8>>>();
+
+	let proved_value = read_proof_check::<Blake2Hasher>(H256::from_slice(&state_root), proof, &key)
+		.map_err(|e| format!("Proof check failed: {:?}", e))?;
+	let value: Option<Vec<u8>> = bundle.value.clone().map(Into::into);
+	let value_valid = proved_value == value;
+
+	#[derive(Serialize)]
+	struct Output {
+		valid: bool,
+		trusted: bool,
+		header_valid: bool,
+		value_valid: bool,
+		number: u64,
+		hash: Hex,
+		state_root: Hex,
+		key: Hex,
+		value: Option<Hex>,
+	}
+
+	let output = Output {
+		valid: trusted && header_valid && value_valid,
+		trusted,
+		header_valid,
+		value_valid,
+		number: bundle.header.number,
+		hash: bundle.header.hash,
+		state_root: bundle.header.state_root,
+		key: bundle.key,
+		value: proved_value.map(Into::into),
+	};
+
+	base::output(output)
+}
+
+/// Storage read proof with the block header, the block hash is computed from the header,
+/// and the value is checked against the state root of the header
+#[derive(Serialize, Deserialize)]
+struct ProofBundle {
+	header: ProofHeader,
+	key: Hex,
+	value: Option<Hex>,
+	proof: Vec<Hex>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProofHeader {
+	hash: Hex,
+	parent_hash: Hex,
+	number: u64,
+	state_root: Hex,
+	extrinsics_root: Hex,
+	/// Encoded digest items
+	logs: Vec<Hex>,
+}
+
+impl ProofHeader {
+	/// blake2_256 of the encoded header
+	fn compute_hash(&self) -> Vec<u8> {
+		let mut data: Vec<u8> = self.parent_hash.clone().into();
+		Compact(self.number).encode_to(&mut data);
+		data.extend::<Vec<u8>>(self.state_root.clone().into());
+		data.extend::<Vec<u8>>(self.extrinsics_root.clone().into());
+		Compact(self.logs.len() as u32).encode_to(&mut data);
+		for log in &self.logs {
+			data.extend::<Vec<u8>>(log.clone().into());
+		}
+		blake2_256(&data).to_vec()
+	}
+}
+
+fn get_proof_header(rpc: &str, block_hash: &str) -> Result<ProofHeader, String> {
+	let mut runtime = Runtime::new().expect("qed");
+
+	let header = runtime
+		.block_on(base::rpc_call::<_, Value>(
+			rpc,
+			"chain_getHeader",
+			&(block_hash,),
+		))?
+		.result
+		.ok_or(format!("Block hash not found: {}", block_hash))?;
+
+	parse_proof_header(block_hash, &header)
+}
+
+/// Parse the header returned by chain_getHeader
+fn parse_proof_header(block_hash: &str, header: &Value) -> Result<ProofHeader, String> {
+	let field = |name: &str| -> Result<String, String> {
+		header
+			.get(name)
+			.and_then(|x| x.as_str())
+			.map(|x| x.to_string())
+			.ok_or("Decode header failed".to_string())
+	};
+
+	let number = u64::from_str_radix(field("number")?.trim_start_matches("0x"), 16)
+		.map_err(|_| "Decode header failed")?;
+
+	let logs = header
+		.get("digest")
+		.and_then(|x| x.get("logs"))
+		.and_then(|x| x.as_array())
+		.ok_or("Decode header failed")?
+		.iter()
+		.map(|x| Hex::from_str(x.as_str().ok_or("Decode header failed")?))
+		.collect::<Result<Vec<_>, String>>()?;
+
+	Ok(ProofHeader {
+		hash: Hex::from_str(block_hash)?,
+		parent_hash: Hex::from_str(&field("parentHash")?)?,
+		number,
+		state_root: Hex::from_str(&field("stateRoot")?)?,
+		extrinsics_root: Hex::from_str(&field("extrinsicsRoot")?)?,
+		logs,
+	})
+}
+
+/// Decimals of the balance, 1 YEE = 10^8
+pub const BALANCE_DECIMALS: u32 = 8;
+
+#[derive(Serialize)]
+struct Amount {
+	value: u128,
+	units: String,
+}
+
+impl From<u128> for Amount {
+	fn from(value: u128) -> Self {
+		Amount {
+			value,
+			units: format_units(value, BALANCE_DECIMALS),
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct BalanceReportAccount {
+	address: String,
+	shard_num: u16,
+	nonce: u64,
+	free: Amount,
+	reserved: Amount,
+	total: Amount,
+}
+
+#[derive(Serialize)]
+struct BalanceReportTotal {
+	free: Amount,
+	reserved: Amount,
+	total: Amount,
+}
+
+#[derive(Serialize)]
+struct BalanceReportIssuance {
+	shard_num: u16,
+	total_issuance: Amount,
+}
+
+#[derive(Serialize)]
+struct BalanceReport {
+	accounts: Vec<BalanceReportAccount>,
+	total: BalanceReportTotal,
+	total_issuance: Vec<BalanceReportIssuance>,
+}
+
+impl BalanceReport {
+	fn to_table(&self) -> Vec<String> {
+		let mut rows = vec![vec![
+			"address".to_string(),
+			"shard".to_string(),
+			"nonce".to_string(),
+			"free".to_string(),
+			"reserved".to_string(),
+			"total".to_string(),
+		]];
+		let amount = |x: &Amount| format!("{} ({})", x.value, x.units);
+		for account in &self.accounts {
+			rows.push(vec![
+				account.address.clone(),
+				account.shard_num.to_string(),
+				account.nonce.to_string(),
+				amount(&account.free),
+				amount(&account.reserved),
+				amount(&account.total),
+			]);
+		}
+		rows.push(vec![
+			"total".to_string(),
+			"".to_string(),
+			"".to_string(),
+			amount(&self.total.free),
+			amount(&self.total.reserved),
+			amount(&self.total.total),
+		]);
+		for issuance in &self.total_issuance {
+			rows.push(vec![
+				"total issuance".to_string(),
+				issuance.shard_num.to_string(),
+				"".to_string(),
+				"".to_string(),
+				"".to_string(),
+				amount(&issuance.total_issuance),
+			]);
+		}
+
+		let mut rows = rows
+			.into_iter()
+			.map(|row| Row::new(row.iter().map(|cell| Cell::new(cell)).collect()));
+		let mut table = Table::new();
+		table.set_titles(rows.next().expect("qed"));
+		for row in rows {
+			table.add_row(row);
+		}
+		table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+		vec![table.to_string()]
+	}
+
+	fn to_csv(&self) -> Vec<String> {
+		let mut lines = vec![
+			"address,shard,nonce,free,free_units,reserved,reserved_units,total,total_units"
+				.to_string(),
+		];
+		for account in &self.accounts {
+			lines.push(format!(
+				"{},{},{},{},{},{},{},{},{}",
+				account.address,
+				account.shard_num,
+				account.nonce,
+				account.free.value,
+				account.free.units,
+				account.reserved.value,
+				account.reserved.units,
+				account.total.value,
+				account.total.units
+			));
+		}
+		lines.push(format!(
+			"total,,,{},{},{},{},{},{}",
+			self.total.free.value,
+			self.total.free.units,
+			self.total.reserved.value,
+			self.total.reserved.units,
+			self.total.total.value,
+			self.total.total.units
+		));
+		for issuance in &self.total_issuance {
+			lines.push(format!(
+				"total issuance,{},,,,,,{},{}",
+				issuance.shard_num, issuance.total_issuance.value, issuance.total_issuance.units
+			));
+		}
+		lines
+	}
+}
+
+fn get_balance_report(
+	matches: &ArgMatches,
+	addresses: &[String],
+	number: Option<u64>,
+) -> Result<BalanceReport, String> {
+	// shard num and shard count of the rpc
+	let mut shards = BTreeMap::new();
+
+	let mut accounts = vec![];
+	for address in addresses {
+		let (public_key, _hrp) = <[u8; 32]>::from_address(&Address(address.clone()))
+			.map_err(|_| format!("Address decode failed: {}", address))?;
+
+		let rpc = base::get_rpc_by_public_key(matches, &public_key)?;
+
+		let (rpc_shard_num, shard_count) = match shards.get(&rpc) {
+			Some(shard) => *shard,
+			None => {
+				let shard = get_block_info(Number::Best, &rpc)?
+					.shard
+					.ok_or("Invalid shard info")?;
+				let shard = (shard.shard_num, shard.shard_count);
+				shards.insert(rpc.clone(), shard);
+				shard
+			}
+		};
+
+		// the account is stored only in its own shard, a single --rpc may serve another shard
+		let shard_num =
+			shard_num_for_bytes(&public_key, shard_count).ok_or("Invalid shard count")?;
+		if shard_num != rpc_shard_num {
+			return Err(format!(
+				"Address in shard {} but the rpc in shard {}: {}, use --shard-rpc or a profile",
+				shard_num, rpc_shard_num, address
+			));
+		}
+
+		let get_value = |prefix: &[u8]| -> Result<Option<Vec<u8>>, String> {
+			get_storage(&rpc, get_map_storage_key(&public_key, prefix), number)
+		};
+
+		let free = match get_value(b"Balances FreeBalance")? {
+			Some(data) => u128_from_slice(&data)?,
+			None => 0,
+		};
+		let reserved = match get_value(b"Balances ReservedBalance")? {
+			Some(data) => u128_from_slice(&data)?,
+			None => 0,
+		};
+		let nonce = match get_value(b"System AccountNonce")? {
+			Some(data) => u64_from_slice(&data)?,
+			None => 0,
+		};
+
+		accounts.push(BalanceReportAccount {
+			address: address.clone(),
+			shard_num,
+			nonce,
+			free: free.into(),
+			reserved: reserved.into(),
+			total: free.saturating_add(reserved).into(),
+		});
+	}
+
+	let free = accounts
+		.iter()
+		.fold(0u128, |sum, x| sum.saturating_add(x.free.value));
+	let reserved = accounts
+		.iter()
+		.fold(0u128, |sum, x| sum.saturating_add(x.reserved.value));
+
+	let total_issuance = shards
+		.into_iter()
+		.map(|(rpc, (shard_num, _shard_count))| {
+			let total_issuance = match get_storage(
+				&rpc,
+				get_value_storage_key(b"Balances TotalIssuance"),
+				number,
+			)? {
+				Some(data) => u128_from_slice(&data)?,
+				None => 0,
+			};
+			Ok(BalanceReportIssuance {
+				shard_num,
+				total_issuance: total_issuance.into(),
+			})
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	Ok(BalanceReport {
+		accounts,
+		total: BalanceReportTotal {
+			free: free.into(),
+			reserved: reserved.into(),
+			total: free.saturating_add(reserved).into(),
+		},
+		total_issuance,
+	})
+}
+
+/// Format amount in decimal units, e.g. 199975600000 to 1999.756
+pub fn format_units(value: u128, decimals: u32) -> String {
+	let unit = 10u128.pow(decimals);
+	let integer = value / unit;
+	let fraction = value % unit;
+	if fraction == 0 {
+		return integer.to_string();
+	}
+	let fraction = format!("{:0width$}", fraction, width = decimals as usize);
+	format!("{}.{}", integer, fraction.trim_end_matches('0'))
+}
+
+struct KeyItem {
+	key: Vec<u8>,
+	account: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SerdeKeyItem {
+	key: Hex,
+	account: Option<String>,
+}
+
+impl From<KeyItem> for SerdeKeyItem {
+	fn from(t: KeyItem) -> Self {
+		SerdeKeyItem {
+			key: t.key.into(),
+			account: t.account,
+		}
+	}
+}
+
+/// Keys under --prefix existing in any of the blocks, sorted,
+/// map keys are hashed without a common prefix, so they are computed from the known accounts
+fn get_key_items(
+	matches: &ArgMatches,
+	rpc: &str,
+	block_hashes: &[&Option<String>],
+) -> Result<(Option<metadata::StorageMetadata>, Vec<KeyItem>), String> {
+	let prefix = matches.value_of("PREFIX").expect("qed");
+
+	// metadata by the last block
+	let block_hash = *block_hashes.last().expect("qed");
+
+	let get_prefix_keys = |prefix: &[u8]| -> Result<Vec<Vec<u8>>, String> {
+		let mut keys = BTreeSet::new();
+		for block_hash in block_hashes {
+			keys.extend(get_storage_keys(rpc, prefix, block_hash)?);
+		}
+		Ok(keys.into_iter().collect())
+	};
+
+	if prefix.starts_with("0x") {
+		let prefix: Vec<u8> = Hex::from_str(prefix)?.into();
+		if prefix.is_empty() {
+			return Err("Empty prefix: full enumeration is not supported".to_string());
+		}
+		let items = get_prefix_keys(&prefix)?
+			.into_iter()
+			.map(|key| KeyItem { key, account: None })
+			.collect();
+		return Ok((None, items));
+	}
+
+	let names = prefix.split_whitespace().collect::<Vec<_>>();
+	if names.len() != 2 {
+		return Err("Invalid prefix: should be hex or \"Module Storage\"".to_string());
+	}
+
+	let metadata = metadata::get_metadata(rpc, block_hash.clone())?;
+	let (module, storage) = metadata.storage(names[0], names[1])?;
+
+	let items = match storage.key_types().as_slice() {
+		[] => {
+			let key = storage.storage_key(&module.prefix, &[])?;
+			get_prefix_keys(&key)?
+				.into_iter()
+				.map(|key| KeyItem { key, account: None })
+				.collect()
+		}
+		[key_type] if metadata::Type::parse(key_type) == metadata::Type::AccountId => {
+			let accounts = get_accounts(matches)?;
+			if accounts.is_empty() {
+				return Err(
+					"Map keys are hashed without the prefix, full enumeration is not supported: provide known accounts by --account or --accounts-file"
+						.to_string(),
+				);
+			}
+			let hrp_mainnet = is_mainnet(rpc)?;
+			let mut expected = BTreeMap::new();
+			for account in accounts {
+				let key = storage.storage_key(&module.prefix, &[account.clone()])?;
+				let hrp = if hrp_mainnet { Hrp::MAINNET } else { Hrp::TESTNET };
+				let address = account
+					.to_address(hrp)
+					.map_err(|_| "Address encode failed")?;
+				expected.insert(key, address.0);
+			}
+			// get the storage of each map key directly, instead of enumerating the whole state
+			let mut items = vec![];
+			for (key, address) in expected {
+				let mut exists = false;
+				for block_hash in block_hashes {
+					if get_storage_at(rpc, StorageKey(key.clone()), block_hash)?.is_some() {
+						exists = true;
+						break;
+					}
+				}
+				if exists {
+					items.push(KeyItem {
+						key,
+						account: Some(address),
+					});
+				}
+			}
+			items
+		}
+		_ => {
+			return Err(format!(
+				"Unsupported storage: only values and maps keyed by account can be listed, {} {} is keyed by {}",
+				module.name,
+				storage.name,
+				storage.key_types().join(", ")
+			))
+		}
+	};
+
+	Ok((Some(storage.clone()), items))
+}
+
+/// Accounts (public keys) from --account and --accounts-file
+fn get_accounts(matches: &ArgMatches) -> Result<Vec<Vec<u8>>, String> {
+	let mut accounts = match matches.values_of("ACCOUNT") {
+		Some(values) => values
+			.map(parse_public_key)
+			.collect::<Result<Vec<_>, _>>()?,
+		None => vec![],
+	};
+	if let Some(accounts_file) = matches.value_of("ACCOUNTS_FILE") {
+		let content = base::get_from_file(accounts_file)?;
+		let content = String::from_utf8(content).map_err(|_| "Invalid accounts file")?;
+		for line in content.lines().map(str::trim).filter(|x| !x.is_empty()) {
+			accounts.push(parse_public_key(line)?);
+		}
+	}
+	Ok(accounts)
+}
+
+pub fn get_storage_keys(
+	rpc: &str,
+	prefix: &[u8],
+	block_hash: &Option<String>,
+) -> Result<Vec<Vec<u8>>, String> {
+	let mut runtime = Runtime::new().expect("qed");
+
+	let result = runtime.block_on(base::rpc_call::<_, Vec<StorageKey>>(
+		rpc,
+		"state_getKeys",
+		&(StorageKey(prefix.to_vec()), block_hash),
+	))?;
+
+	if let Some(_error) = result.error {
+		return Err("Get storage keys failed".to_string());
+	}
+
+	let result = result
+		.result
+		.unwrap_or_default()
+		.into_iter()
+		.map(|x| x.0)
+		.collect();
+
+	Ok(result)
+}
+
+pub fn u128_from_slice(bytes: &[u8]) -> Result<u128, String> {
+	const LEN: usize = 16;
+	if bytes.len() != LEN {
+		return Err("u128 decode error".to_string());
+	}
+	let mut array = [0; LEN];
+	let bytes = &bytes[..];
+	array.copy_from_slice(bytes);
+	Ok(u128::from_le_bytes(array))
+}
+
+pub fn u64_from_slice(bytes: &[u8]) -> Result<u64, String> {
+	const LEN: usize = 8;
+	if bytes.len() != LEN {
+		return Err("u128 decode error".to_string());
+	}
+	let mut array = [0; LEN];
+	let bytes = &bytes[..];
+	array.copy_from_slice(bytes);
+	Ok(u64::from_le_bytes(array))
+}
+
+pub fn get_value_storage_key(key: &[u8]) -> StorageKey {
+	StorageKey(twox_128(key))
+}
+
+pub fn get_map_storage_key(key: &[u8], prefix: &[u8]) -> StorageKey {
+	let mut prefix = prefix.to_vec();
+	prefix.extend(key);
 	let a = blake2_256(&prefix).to_vec();
 	StorageKey(a)
 }
@@ -341,7 +1848,7 @@ where
 	StorageKey(a)
 }
 
-fn twox_128(data: &[u8]) -> Vec<u8> {
+pub fn twox_128(data: &[u8]) -> Vec<u8> {
 	let hash0 = twox(data, 0);
 	let hash1 = twox(data, 1);
 	let mut result = vec![0u8; 16];
@@ -350,7 +1857,16 @@ fn twox_128(data: &[u8]) -> Vec<u8> {
 	result
 }
 
-fn twox(data: &[u8], seed: u64) -> Vec<u8> {
+pub fn twox_256(data: &[u8]) -> Vec<u8> {
+	let mut result = vec![0u8; 32];
+	for seed in 0..4 {
+		let start = seed as usize * 8;
+		result[start..start + 8].copy_from_slice(&twox(data, seed));
+	}
+	result
+}
+
+pub fn twox(data: &[u8], seed: u64) -> Vec<u8> {
 	use ::core::hash::Hasher;
 	let mut h = twox_hash::XxHash::with_seed(seed);
 	h.write(&data);
@@ -420,6 +1936,220 @@ mod cases {
 						is_example: true,
 						is_test: false,
 						since: "0.6.0".to_string(),
+					},
+					Case {
+						desc: "Get balance routed to the shard of the address".to_string(),
+						input: vec!["balance", "--shard-rpc", "0=http://localhost:9033", "--shard-rpc", "1=http://localhost:19033", "--shard-rpc", "2=http://localhost:29033", "--shard-rpc", "3=http://localhost:39033", "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": 199975600000
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Get balance report of addresses as table".to_string(),
+						input: vec!["balance", "--shard-rpc", "0=http://localhost:9033", "--shard-rpc", "1=http://localhost:19033", "--shard-rpc", "2=http://localhost:29033", "--shard-rpc", "3=http://localhost:39033", "-f", "table", "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj", "yee1wmffva8zfwfvm4d57t7etp4lycml4xgcg2f2v97qk4ensw7r8szqd0acf7"].into_iter().map(Into::into).collect(),
+						output: vec![r#"+----------------------------------------------------------------+-------+-------+-------------------------+----------+-------------------------+
+| address                                                        | shard | nonce | free                    | reserved | total                   |
++----------------------------------------------------------------+-------+-------+-------------------------+----------+-------------------------+
+| yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj | 3     | 1     | 199975600000 (1999.756) | 0 (0)    | 199975600000 (1999.756) |
+| yee1wmffva8zfwfvm4d57t7etp4lycml4xgcg2f2v97qk4ensw7r8szqd0acf7 | 0     | 0     | 100000000 (1)           | 0 (0)    | 100000000 (1)           |
+| total                                                          |       |       | 200075600000 (2000.756) | 0 (0)    | 200075600000 (2000.756) |
+| total issuance                                                 | 0     |       |                         |          | 20000000000000 (200000) |
+| total issuance                                                 | 3     |       |                         |          | 20000000000000 (200000) |
++----------------------------------------------------------------+-------+-------+-------------------------+----------+-------------------------+"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Get balance report of addresses in file as csv".to_string(),
+						input: vec!["balance", "--shard-rpc", "0=http://localhost:9033", "--shard-rpc", "1=http://localhost:19033", "--shard-rpc", "2=http://localhost:29033", "--shard-rpc", "3=http://localhost:39033", "-f", "csv", "--file", "addresses.txt"].into_iter().map(Into::into).collect(),
+						output: vec![r#"address,shard,nonce,free,free_units,reserved,reserved_units,total,total_units
+yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj,3,1,199975600000,1999.756,0,0,199975600000,1999.756
+yee1wmffva8zfwfvm4d57t7etp4lycml4xgcg2f2v97qk4ensw7r8szqd0acf7,0,0,100000000,1,0,0,100000000,1
+total,,,200075600000,2000.756,0,0,200075600000,2000.756
+total issuance,0,,,,,,20000000000000,200000
+total issuance,3,,,,,,20000000000000,200000"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Get storage decoded by runtime metadata".to_string(),
+						input: vec!["get", "-r", "http://localhost:9033", "balances", "FreeBalance", "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "module": "balances",
+    "item": "FreeBalance",
+    "type": "T::Balance",
+    "modifier": "Default",
+    "key": "0xebf2102fba1ab013871465dbffdc9b5b097c1759ddb494dccc18ed3a5e7f1a8a",
+    "raw": "0x807f798f2e0000000000000000000000",
+    "value": 199975600000
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Get storage keys of known accounts".to_string(),
+						input: vec!["keys", "-r", "http://localhost:9033", "-p", "'Balances FreeBalance'", "-a", "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "keys": [
+      {
+        "key": "0xebf2102fba1ab013871465dbffdc9b5b097c1759ddb494dccc18ed3a5e7f1a8a",
+        "account": "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj"
+      }
+    ],
+    "next_start_key": null
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Dump storage of known accounts to a json file".to_string(),
+						input: vec!["dump", "-r", "http://localhost:9033", "-p", "'Balances FreeBalance'", "--accounts-file", "accounts.txt", "--at", "100", "-o", "dump.json"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "number": 100,
+    "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+    "count": 1,
+    "output": "dump.json"
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Diff balances of known accounts between two blocks".to_string(),
+						input: vec!["diff", "-r", "http://localhost:9033", "--from", "99", "--to", "100", "-p", "'Balances FreeBalance'", "-a", "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "from": {
+      "number": 99,
+      "hash": "0x5d3c4a1f4f5e0bd7a4b2a6bcbd0d0f5c0a7d9f0f7f8e52a1b6b4b3f7f9c6e1d2"
+    },
+    "to": {
+      "number": 100,
+      "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39"
+    },
+    "added": [],
+    "removed": [],
+    "changed": [
+      {
+        "key": "0xebf2102fba1ab013871465dbffdc9b5b097c1759ddb494dccc18ed3a5e7f1a8a",
+        "account": "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj",
+        "old_raw": "0x80e9918f2e0000000000000000000000",
+        "new_raw": "0x807f798f2e0000000000000000000000",
+        "old": 199977200000,
+        "new": 199975600000,
+        "delta": "-1600000"
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Get balance history".to_string(),
+						input: vec!["balance_history", "-r", "http://localhost:9033", "--from", "90", "--to", "100", "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "address": "yee1x6c3d0x7la4lvdfee63u4lwepw6n6m0sgwew77gaydxf9jjaaqzqzzu8dj",
+    "from": 90,
+    "to": 100,
+    "initial_balance": 199977200000,
+    "history": [
+      {
+        "number": 100,
+        "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+        "timestamp": 1585718431296,
+        "balance": 199975600000,
+        "delta": "-1600000",
+        "extrinsics": [
+          {
+            "index": 2,
+            "hash": "0x8f7a0e3c2bd7d8f1b0a3a1e1f0e2d4c6b5a49382716f5e4d3c2b1a0918273645",
+            "tx": {
+              "signature": {
+                "sender": "0xff36b116bcdeff6bf63539cea3cafdd90bb53d6df043b2ef791d234c92ca5de804",
+                "signature": "0x0cf2bd4b2c0ab7a08c9e7d1fe0d1e6d9e1a0f3a5e8b6ed1a9b2b57c3e9a4e45e9b6d8b2fc0a4d7e1c2b3a495867768594a3b2c1d0e1f2a3b4c5d6e7f8091a28c",
+                "nonce": 1,
+                "era": {
+                  "Mortal": [
+                    64,
+                    36
+                  ]
+                }
+              },
+              "call": {
+                "module": 4,
+                "method": 0,
+                "params": {
+                  "dest": "0xff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d",
+                  "value": 1000000
+                }
+              }
+            },
+            "success": true,
+            "events": [
+              "balances(Transfer(36b116bcdeff6bf63539cea3cafdd90bb53d6df043b2ef791d234c92ca5de804 (5DHmJ5rs...), 94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d (5FRsZjZU...), 1000000, 600000))"
+            ]
+          }
+        ],
+        "events": [
+          "EventRecord { phase: ApplyExtrinsic(2), event: balances(Transfer(36b116bcdeff6bf63539cea3cafdd90bb53d6df043b2ef791d234c92ca5de804 (5DHmJ5rs...), 94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d (5FRsZjZU...), 1000000, 600000)) }"
+        ]
+      }
+    ]
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Get storage read proof bundle to a json file".to_string(),
+						input: vec!["proof", "-r", "http://localhost:9033", "--at", "100", "-o", "proof.json", "0xebf2102fba1ab013871465dbffdc9b5b097c1759ddb494dccc18ed3a5e7f1a8a"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "number": 100,
+    "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+    "output": "proof.json"
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
+					},
+					Case {
+						desc: "Verify storage read proof bundle offline".to_string(),
+						input: vec!["verify_proof", "--hash", "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39", "proof.json"].into_iter().map(Into::into).collect(),
+						output: vec![r#"{
+  "result": {
+    "valid": true,
+    "trusted": true,
+    "header_valid": true,
+    "value_valid": true,
+    "number": 100,
+    "hash": "0x2dd7b2d9dd3f0e3fd2fb5e1fa34d42d2a2e2d5d4e5c4cb0d6e7e1b4d5c7e6a39",
+    "state_root": "0x7c1f3e1b8d0a9e6f5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a",
+    "key": "0xebf2102fba1ab013871465dbffdc9b5b097c1759ddb494dccc18ed3a5e7f1a8a",
+    "value": "0x807f798f2e0000000000000000000000"
+  }
+}"#].into_iter().map(Into::into).collect(),
+						is_example: true,
+						is_test: false,
+						since: "0.14.0".to_string(),
 					}
                 ],
             ),
@@ -427,11 +2157,41 @@ mod cases {
 	}
 }
 
-fn get_storage(
+pub fn get_storage(
 	rpc: &str,
 	storage_key: StorageKey,
 	block_number: Option<u64>,
 ) -> Result<Option<Vec<u8>>, String> {
+	let block_hash = get_block_hash(rpc, block_number)?;
+
+	get_storage_at(rpc, storage_key, &block_hash)
+}
+
+pub fn get_storage_at(
+	rpc: &str,
+	storage_key: StorageKey,
+	block_hash: &Option<String>,
+) -> Result<Option<Vec<u8>>, String> {
+	let mut runtime = Runtime::new().expect("qed");
+
+	let result = runtime.block_on(base::rpc_call::<_, Option<StorageData>>(
+		rpc,
+		"state_getStorage",
+		&(&storage_key, block_hash),
+	))?;
+
+	if let Some(_error) = result.error {
+		return Err("Get storage failed".to_string());
+	}
+
+	let result = result.result.unwrap_or(None);
+
+	let result = result.map(|x| x.0);
+
+	Ok(result)
+}
+
+pub fn get_block_hash(rpc: &str, block_number: Option<u64>) -> Result<Option<String>, String> {
 	let mut runtime = Runtime::new().expect("qed");
 
 	let block_hash = match block_number {
@@ -449,25 +2209,15 @@ fn get_storage(
 		None => None,
 	};
 
-	let result = runtime.block_on(base::rpc_call::<_, Option<StorageData>>(
-		rpc,
-		"state_getStorage",
-		&(&storage_key, &block_hash),
-	))?;
-
-	if let Some(_error) = result.error {
-		return Err("Get storage failed".to_string());
-	}
-
-	let result = result.result.unwrap_or(None);
-
-	let result = result.map(|x| x.0);
-
-	Ok(result)
+	Ok(block_hash)
 }
 
 #[cfg(test)]
 mod tests {
+	use runtime_primitives::generic::{Digest, DigestItem};
+	use runtime_primitives::traits::{Block as BlockT, Header as HeaderT};
+	use yee_runtime::opaque::Block;
+
 	use crate::modules::base::test::test_module;
 
 	use super::*;
@@ -476,4 +2226,72 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_compute_hash() {
+		type Header = <Block as BlockT>::Header;
+
+		let logs = vec![
+			DigestItem::Other(vec![1, 2, 3]),
+			DigestItem::Other(hex::decode("0102030405060708").unwrap()),
+		];
+		let header = Header::new(
+			1_234_567,
+			H256::from_slice(&[1u8; 32]),
+			H256::from_slice(&[2u8; 32]),
+			H256::from_slice(&[3u8; 32]),
+			Digest { logs },
+		);
+		let block_hash = format!("0x{}", hex::encode(header.hash()));
+
+		// the same as chain_getHeader returns
+		let json = serde_json::to_value(&header).unwrap();
+		let proof_header = parse_proof_header(&block_hash, &json).unwrap();
+
+		assert_eq!(proof_header.number, 1_234_567);
+		assert_eq!(proof_header.logs.len(), 2);
+		assert_eq!(Hex::from(proof_header.compute_hash()), proof_header.hash);
+	}
+
+	#[test]
+	fn test_format_units() {
+		assert_eq!(format_units(0, BALANCE_DECIMALS), "0");
+		assert_eq!(format_units(100000000, BALANCE_DECIMALS), "1");
+		assert_eq!(format_units(200000000000, BALANCE_DECIMALS), "2000");
+		assert_eq!(format_units(199975600000, BALANCE_DECIMALS), "1999.756");
+		assert_eq!(format_units(150000000, BALANCE_DECIMALS), "1.5");
+		assert_eq!(format_units(1, BALANCE_DECIMALS), "0.00000001");
+		assert_eq!(format_units(12345000, BALANCE_DECIMALS), "0.12345");
+		assert_eq!(
+			format_units(u128::max_value(), BALANCE_DECIMALS),
+			"3402823669209384634633746074317.68211455"
+		);
+	}
+
+	fn find_changes(balances: &[u128], step: u64) -> Vec<u64> {
+		let to = balances.len() as u64 - 1;
+		let mut balance_at = |number: u64| Ok(balances[number as usize]);
+		let mut changes = vec![];
+		let mut start = 0;
+		while start < to {
+			let end = min(start + step, to);
+			find_balance_changes(start, end, &mut balance_at, &mut changes).unwrap();
+			start = end;
+		}
+		changes
+	}
+
+	#[test]
+	fn test_find_balance_changes() {
+		let balances = [10, 10, 20, 20, 20, 15, 15, 30, 30];
+		assert_eq!(find_changes(&balances, 8), vec![2, 5, 7]);
+		assert_eq!(find_changes(&balances, 3), vec![2, 5, 7]);
+		assert_eq!(find_changes(&balances, 1), vec![2, 5, 7]);
+
+		// A -> B -> A within a span is skipped
+		let balances = [10, 10, 20, 20, 10, 10, 10, 10, 10];
+		assert_eq!(find_changes(&balances, 8), Vec::<u64>::new());
+		assert_eq!(find_changes(&balances, 4), Vec::<u64>::new());
+		assert_eq!(find_changes(&balances, 3), vec![2, 4]);
+	}
 }
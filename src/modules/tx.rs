@@ -1,4 +1,5 @@
-use std::cmp::min;
+use std::cmp::{max, min};
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use clap::{Arg, ArgMatches, SubCommand};
@@ -26,11 +27,16 @@ use yee_signer::{KeyPair, PUBLIC_KEY_LEN, SECRET_KEY_LEN};
 
 use lazy_static::lazy_static;
 
-use crate::modules::account::{Shard, SHARD_COUNT_LIST};
+use crate::modules::account::{parse_public_key, shard_count_list, verify_signature, Shard};
+use crate::modules::agent;
 use crate::modules::base::{get_rpc, Hex, RpcResponse};
 use crate::modules::keystore::get_keystore;
-use crate::modules::meter::{get_block_info, get_hrp, Number};
-use crate::modules::state::{get_map_storage_key_encode, get_value_storage_key};
+use crate::modules::metadata::{get_metadata, Type};
+use crate::modules::meter::{get_block_info, get_hrp, BlockInfo, Number};
+use crate::modules::state::{
+	get_map_storage_key, get_map_storage_key_encode, get_storage_at, get_value_storage_key,
+	u128_from_slice,
+};
 use crate::modules::{base, Command, Module};
 
 pub fn module<'a, 'b>() -> Module<'a, 'b> {
@@ -58,59 +64,138 @@ fn run(matches: &ArgMatches) -> Result<Vec<String>, String> {
 fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 	vec![
 		Command {
-			app: SubCommand::with_name("desc").about("Desc tx").arg(
-				Arg::with_name("INPUT")
-					.help("raw tx")
-					.required(false)
-					.index(1),
-			),
+			app: SubCommand::with_name("desc")
+				.about("Desc tx")
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, to verify the origin tx of relay")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("INPUT")
+						.help("raw tx")
+						.required(false)
+						.index(1),
+				),
 			f: desc,
 		},
 		Command {
-			app: SubCommand::with_name("compose")
-				.about("Compose tx")
+			app: SubCommand::with_name("verify")
+				.about("Verify the signature of tx, and check the era, nonce, balance and shard with rpc")
 				.arg(
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: to get the checkpoint hash and check the tx against the node")
 						.takes_value(true)
-						.required(true),
-				)
-				.arg(
-					Arg::with_name("KEYSTORE_PATH")
-						.long("keystore-path")
-						.short("k")
-						.help("Keystore path")
-						.takes_value(true)
-						.required(true),
+						.required(false),
 				)
 				.arg(
-					Arg::with_name("NONCE")
-						.long("nonce")
-						.short("n")
-						.help("Nonce: get from node for default")
+					Arg::with_name("CHECKPOINT_HASH")
+						.long("checkpoint-hash")
+						.help("Checkpoint hash: the block hash of the era birth (the genesis hash if immortal), get from node for default")
 						.takes_value(true)
 						.required(false),
 				)
 				.arg(
-					Arg::with_name("PERIOD")
-						.long("period")
-						.short("p")
-						.help("Period: 64 for default")
+					Arg::with_name("NUMBER")
+						.long("number")
+						.help("Block number to check the era at: the best block for default; a tx signed in an expired era is reported with era_valid false")
 						.takes_value(true)
 						.required(false),
 				)
+				.arg(
+					Arg::with_name("INPUT")
+						.help("raw tx")
+						.required(false)
+						.index(1),
+				),
+			f: verify,
+		},
+		Command {
+			app: SubCommand::with_name("compose")
+				.about("Compose tx")
+				.args(&compose_args())
 				.arg(
 					Arg::with_name("CALL")
 						.long("call")
 						.short("c")
-						.help("Call: json")
+						.help("Call: json with module and method by index or name, or raw call hex")
 						.takes_value(true)
 						.required(true),
 				),
 			f: compose,
 		},
+		Command {
+			app: SubCommand::with_name("sudo")
+				.about("Compose sudo tx: wrap the proposal call in sudo")
+				.args(&compose_args())
+				.arg(
+					Arg::with_name("CALL")
+						.long("call")
+						.short("c")
+						.help("Proposal call: json with module and method by index or name, or raw call hex, - for stdin")
+						.takes_value(true)
+						.conflicts_with_all(&["SET_CODE", "SET_STORAGE", "KILL_STORAGE", "SHARDING"])
+						.required_unless_one(&["SET_CODE", "SET_STORAGE", "KILL_STORAGE", "SHARDING"]),
+				)
+				.arg(
+					Arg::with_name("SET_CODE")
+						.long("set-code")
+						.help("Proposal to set code: code path")
+						.takes_value(true)
+						.conflicts_with_all(&["SET_STORAGE", "KILL_STORAGE", "SHARDING"])
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SET_STORAGE")
+						.long("set-storage")
+						.help("Proposal to set storage: key=value in hex, by consensus set_storage as the raw storage calls are in the consensus module of substrate 1.0")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with_all(&["KILL_STORAGE", "SHARDING"])
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("KILL_STORAGE")
+						.long("kill-storage")
+						.help("Proposal to kill storage: key in hex, by consensus kill_storage as the raw storage calls are in the consensus module of substrate 1.0")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("SHARDING")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARDING")
+						.long("sharding")
+						.help("Proposal to change sharding parameters: method of the sharding module, with the arguments by --sharding-arg")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARDING_ARG")
+						.long("sharding-arg")
+						.help("Argument of the sharding method in order: number, address or hex, encoded by the type in the runtime metadata")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.requires("SHARDING")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("PREVIEW")
+						.long("preview")
+						.help("Preview the decoded call without composing tx")
+						.required(false),
+				),
+			f: sudo,
+		},
 		Command {
 			app: SubCommand::with_name("submit")
 				.about("Submit tx")
@@ -118,9 +203,19 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, route to the shard of the account")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.conflicts_with("RPC")
+						.required(false),
 				)
 				.arg(Arg::with_name("INPUT").required(false).index(1)),
 			f: submit,
@@ -132,9 +227,9 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 					Arg::with_name("RPC")
 						.long("rpc")
 						.short("r")
-						.help("RPC address")
+						.help("RPC address: the profile for default")
 						.takes_value(true)
-						.required(true),
+						.required(false),
 				)
 				.arg(
 					Arg::with_name("HASH")
@@ -153,7 +248,63 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 				.arg(
 					Arg::with_name("SENDER")
 						.long("sender")
-						.help("TX sender")
+						.help("TX sender: address, public key or account hex")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("DEST")
+						.long("dest")
+						.help("Transfer dest: address, public key or account hex")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MODULE")
+						.long("module")
+						.help("Call module: name or index")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("METHOD")
+						.long("method")
+						.help("Call method: name or index")
+						.takes_value(true)
+						.requires("MODULE")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MIN_VALUE")
+						.long("min-value")
+						.help("Min transfer value")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("MAX_VALUE")
+						.long("max-value")
+						.help("Max transfer value")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("SUCCESS")
+						.long("success")
+						.help("Only successful tx")
+						.conflicts_with("FAILED")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("FAILED")
+						.long("failed")
+						.help("Only failed tx")
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("NONCE")
+						.long("nonce")
+						.help("TX nonce")
 						.takes_value(true)
 						.required(false),
 				)
@@ -176,9 +327,46 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 						.long("include-inherent")
 						.help("Whether include inherent")
 						.required(false),
+				)
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, to verify the origin tx of relay")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.required(false),
 				),
 			f: search,
 		},
+		Command {
+			app: SubCommand::with_name("track")
+				.about("Track cross-shard transfer through the relay flow")
+				.arg(
+					Arg::with_name("SHARD_RPC")
+						.long("shard-rpc")
+						.help("Shard RPC address: shard_num=rpc, the profile for default")
+						.takes_value(true)
+						.multiple(true)
+						.number_of_values(1)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("BLOCKS")
+						.long("blocks")
+						.short("b")
+						.help("Blocks to search backward from the best block: 100 for default")
+						.takes_value(true)
+						.required(false),
+				)
+				.arg(
+					Arg::with_name("HASH")
+						.help("TX hash")
+						.required(true)
+						.index(1),
+				),
+			f: track,
+		},
 		Command {
 			app: SubCommand::with_name("make_set_code_call")
 				.about("Make set code call")
@@ -203,6 +391,51 @@ fn sub_commands<'a, 'b>() -> Vec<Command<'a, 'b>> {
 	]
 }
 
+/// Args to compose and sign tx, shared by the commands composing a call
+pub fn compose_args<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+	vec![
+		Arg::with_name("RPC")
+			.long("rpc")
+			.short("r")
+			.help("RPC address: the profile for default")
+			.takes_value(true)
+			.required(false),
+		Arg::with_name("SHARD_RPC")
+			.long("shard-rpc")
+			.help("Shard RPC address: shard_num=rpc, route to the shard of the account")
+			.takes_value(true)
+			.multiple(true)
+			.number_of_values(1)
+			.conflicts_with("RPC")
+			.required(false),
+		Arg::with_name("KEYSTORE_PATH")
+			.long("keystore-path")
+			.short("k")
+			.help("Keystore path: the profile for default")
+			.takes_value(true)
+			.required(false),
+		Arg::with_name("AGENT")
+			.long("agent")
+			.short("a")
+			.help("Agent unix socket path: sign by the keystore agent")
+			.takes_value(true)
+			.conflicts_with("KEYSTORE_PATH")
+			.required(false),
+		Arg::with_name("NONCE")
+			.long("nonce")
+			.short("n")
+			.help("Nonce: get from node for default")
+			.takes_value(true)
+			.required(false),
+		Arg::with_name("PERIOD")
+			.long("period")
+			.short("p")
+			.help("Period: 64 for default")
+			.takes_value(true)
+			.required(false),
+	]
+}
+
 fn desc(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
@@ -210,21 +443,205 @@ fn desc(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	let tx: Transaction = Decode::decode(&mut &input[..]).ok_or("invalid tx")?;
 
-	let tx: SerdeTransaction = tx.into();
+	let shard_rpc_map = base::get_shard_rpc_map(matches)?;
+
+	let relay_signature_valid = RelayVerifier::new(&shard_rpc_map).verify(&tx);
+
+	let mut tx: SerdeTransaction = tx.into();
+
+	set_relay_signature_valid(&mut tx, relay_signature_valid);
 
 	base::output(&tx)
 }
 
+fn verify(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let input = base::input_string(matches)?;
+
+	let input: Vec<u8> = input.parse::<Hex>().map_err(|_| "Convert failed")?.into();
+
+	let tx: Transaction = Decode::decode(&mut &input[..]).ok_or("invalid tx")?;
+
+	let (account, signature, nonce, tx_era) = tx.signature.as_ref().ok_or("Unsigned tx")?;
+	let public_key = {
+		let mut tmp = [0u8; PUBLIC_KEY_LEN];
+		tmp.copy_from_slice(&account.0[1..]);
+		tmp
+	};
+	let nonce = nonce.0;
+
+	let rpc = match matches.value_of("RPC") {
+		Some(_) => Some(get_rpc(matches)?),
+		None => None,
+	};
+
+	let best = match &rpc {
+		Some(rpc) => Some(get_block_info(Number::Best, rpc)?),
+		None => None,
+	};
+
+	let number = match matches.value_of("NUMBER") {
+		Some(number) => Some(number.parse::<u64>().map_err(|_| "Invalid number")?),
+		None => best.as_ref().map(|x| x.number),
+	};
+
+	let verify_by = |hash: &[u8; HASH_LEN]| {
+		let era = match tx_era {
+			Era::Immortal => Era::Immortal,
+			Era::Mortal(period, phase) => Era::Mortal(*period, *phase),
+		};
+		let payload = signing_payload(nonce, &tx.call, era, *hash);
+		verify_signature(&public_key, &payload, &signature.to_vec())
+	};
+
+	// era_valid is None if not able to check the era against the node
+	let (current_hash, signature_valid, era_valid) =
+		match (matches.value_of("CHECKPOINT_HASH"), &rpc, number) {
+			(Some(hash), ..) => {
+				let hash = to_hash(&Vec::<u8>::from(Hex::from_str(hash)?))?;
+				let signature_valid = verify_by(&hash)?;
+				let era_valid = match (&rpc, number) {
+					(Some(rpc), Some(number)) => {
+						let birth = *era_births(tx_era, number)?.first().expect("qed");
+						Some(signature_valid && get_checkpoint_hash(rpc, birth)? == hash)
+					}
+					_ => None,
+				};
+				(hash, signature_valid, era_valid)
+			}
+			(None, Some(rpc), Some(number)) => {
+				let births = era_births(tx_era, number)?;
+				let hash = get_checkpoint_hash(rpc, births[0])?;
+				let mut result = (hash, verify_by(&hash)?, Some(true));
+				if !result.1 {
+					result.2 = Some(false);
+					// verified by an older birth: signed in an expired era
+					for birth in &births[1..] {
+						let hash = get_checkpoint_hash(rpc, *birth)?;
+						if verify_by(&hash)? {
+							result = (hash, true, Some(false));
+							break;
+						}
+					}
+				}
+				result
+			}
+			_ => return Err("Checkpoint hash or rpc required".to_string()),
+		};
+
+	let check = match (&rpc, &best, era_valid) {
+		(Some(rpc), Some(best), Some(era_valid)) => {
+			Some(check_tx(rpc, best, &tx, public_key, era_valid)?)
+		}
+		_ => None,
+	};
+
+	let valid = signature_valid && check.as_ref().map(TxCheck::valid).unwrap_or(true);
+
+	let result = VerifyResult {
+		sender_address: public_key
+			.to_address(Hrp::MAINNET)
+			.map_err(|_e| "Address encode failed")?
+			.0,
+		nonce,
+		checkpoint_hash: current_hash.to_vec().into(),
+		signature_valid,
+		check,
+		valid,
+	};
+
+	base::output(result)
+}
+
+/// Check the tx against the state of the node: nonce, balance and shard, with the era checked by the caller
+fn check_tx(
+	rpc: &str,
+	best: &BlockInfo,
+	tx: &Transaction,
+	public_key: [u8; PUBLIC_KEY_LEN],
+	era_valid: bool,
+) -> Result<TxCheck, String> {
+	let (_, _, nonce, _) = tx.signature.as_ref().ok_or("Unsigned tx")?;
+
+	let account_nonce = get_nonce(public_key, rpc)?;
+
+	let storage_key = get_map_storage_key(&public_key, b"Balances FreeBalance");
+	let free_balance = match get_storage_at(rpc, storage_key, &None)? {
+		Some(data) => u128_from_slice(&data)?,
+		None => 0,
+	};
+
+	let value_enough = match &tx.call {
+		Call::Balances(call::balances::Call::Transfer(transfer)) => {
+			Some(free_balance >= transfer.value.0)
+		}
+		_ => None,
+	};
+
+	let node_shard = best.shard.as_ref().ok_or("Invalid shard info")?;
+	let sender_shard_num =
+		shard_num_for_bytes(&public_key, node_shard.shard_count).ok_or("Invalid shard count")?;
+
+	Ok(TxCheck {
+		era_valid,
+		nonce_valid: nonce.0 >= account_nonce,
+		account_nonce,
+		value_enough,
+		free_balance,
+		shard_match: sender_shard_num == node_shard.shard_num,
+		node_shard: Shard {
+			shard_num: node_shard.shard_num,
+			shard_count: node_shard.shard_count,
+		},
+	})
+}
+
 fn compose(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let call = get_call(matches, &get_call_names(matches)?)?;
 
-	let keystore_path = matches.value_of("KEYSTORE_PATH").expect("qed");
+	let result = compose_call(matches, call)?;
 
-	let period = match matches.value_of("PERIOD") {
-		Some(period) => period.parse::<u64>().map_err(|_| "Invalid period")?,
-		None => 64,
+	base::output(result)
+}
+
+fn sudo(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	// preview offline by the built-in names, unless --rpc given
+	let call_names = if matches.is_present("PREVIEW") && !matches.is_present("RPC") {
+		CallNames::offline()
+	} else {
+		get_call_names(matches)?
+	};
+
+	let proposal = get_proposal(matches, &call_names)?;
+
+	// a sudo call (e.g. set_key) is composed as is
+	let call = match proposal {
+		Call::Sudo(sudo) => Call::Sudo(sudo),
+		proposal => build_sudo_call(proposal),
+	};
+
+	if matches.is_present("PREVIEW") {
+		let output = CallPreview {
+			raw: call.encode().into(),
+			call: call.into(),
+		};
+		return base::output(output);
+	}
+
+	// check before signing, so a wrong signer fails without composing a tx
+	let signer = get_signer(matches)?;
+	check_sudo_key(matches, &signer.public_key()?)?;
+
+	let compose = compose_call_by(matches, signer, call.clone())?;
+
+	let output = ComposeOutput {
+		compose,
+		call: call.into(),
 	};
 
+	base::output(output)
+}
+
+fn get_call(matches: &ArgMatches, call_names: &CallNames) -> Result<Call, String> {
 	let call = matches.value_of("CALL").expect("qed");
 
 	let call_cow = match call {
@@ -232,7 +649,233 @@ fn compose(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		call => Cow::Borrowed(call),
 	};
 
-	let call = call_cow.as_ref();
+	parse_call(&call_cow, call_names)
+}
+
+/// Proposal call of sudo: set code, set storage, kill storage, sharding parameters or the call given
+fn get_proposal(matches: &ArgMatches, call_names: &CallNames) -> Result<Call, String> {
+	if let Some(code_path) = matches.value_of("SET_CODE") {
+		let code = base::get_from_file(code_path)?;
+		return Ok(Call::Consensus(call::consensus::Call::SetCode(
+			call::consensus::SetCode { new: Bytes(code) },
+		)));
+	}
+
+	if let Some(items) = matches.values_of("SET_STORAGE") {
+		let items = items
+			.map(parse_storage_item)
+			.collect::<Result<Vec<_>, _>>()?;
+		return build_raw_call(call_names, "consensus", "set_storage", &items);
+	}
+
+	if let Some(keys) = matches.values_of("KILL_STORAGE") {
+		let keys = keys
+			.map(|x| Ok(Hex::from_str(x)?.into()))
+			.collect::<Result<Vec<Vec<u8>>, String>>()?;
+		return build_raw_call(call_names, "consensus", "kill_storage", &keys);
+	}
+
+	if let Some(method) = matches.value_of("SHARDING") {
+		let args = matches
+			.values_of("SHARDING_ARG")
+			.map(|x| x.collect::<Vec<_>>())
+			.unwrap_or_default();
+		return build_sharding_call(matches, call_names, method, &args);
+	}
+
+	get_call(matches, call_names)
+}
+
+/// Sharding call with the arguments encoded by the types in the runtime metadata
+fn build_sharding_call(
+	matches: &ArgMatches,
+	call_names: &CallNames,
+	method: &str,
+	args: &[&str],
+) -> Result<Call, String> {
+	let rpc = get_metadata_rpc(matches)?.ok_or("RPC required for the sharding method arguments")?;
+	let metadata = get_metadata(&rpc, None)?;
+	let function = metadata
+		.modules
+		.iter()
+		.find(|x| x.name == "sharding")
+		.and_then(|x| x.calls.as_ref()?.iter().find(|x| x.name == method))
+		.ok_or(format!("Unknown sharding method: {}", method))?;
+
+	if function.arguments.len() != args.len() {
+		let names = function
+			.arguments
+			.iter()
+			.map(|x| format!("{}: {}", x.name, x.ty))
+			.collect::<Vec<_>>();
+		return Err(format!(
+			"Sharding arguments required: ({})",
+			names.join(", ")
+		));
+	}
+
+	let mut params = vec![];
+	for (argument, arg) in function.arguments.iter().zip(args) {
+		params.extend(Type::parse(&argument.ty).encode(arg)?);
+	}
+
+	decode_raw_call(call_names, "sharding", method, params)
+}
+
+/// Parse storage item: key=value in hex
+fn parse_storage_item(input: &str) -> Result<(Vec<u8>, Vec<u8>), String> {
+	let mut split = input.splitn(2, '=');
+	match (split.next(), split.next()) {
+		(Some(key), Some(value)) => Ok((Hex::from_str(key)?.into(), Hex::from_str(value)?.into())),
+		_ => Err(format!("Invalid storage item: {}", input)),
+	}
+}
+
+/// The sudo tx fails in the runtime if not signed by the sudo key, so check before submitting
+fn check_sudo_key(matches: &ArgMatches, public_key: &[u8]) -> Result<(), String> {
+	let rpc = &base::get_rpc_by_public_key(matches, public_key)?;
+
+	let sudo_key = get_storage_at(rpc, get_value_storage_key(b"Sudo Key"), &None)?
+		.ok_or("Sudo key not found")?;
+
+	if sudo_key != public_key {
+		let sender_address = public_key
+			.to_vec()
+			.to_address(Hrp::MAINNET)
+			.map_err(|_e| "Address encode failed")?
+			.0;
+		let sudo_address = sudo_key
+			.to_address(Hrp::MAINNET)
+			.map_err(|_e| "Address encode failed")?
+			.0;
+		return Err(format!(
+			"the signer ({}) is not the sudo key ({})",
+			sender_address, sudo_address
+		));
+	}
+
+	Ok(())
+}
+
+/// Parse call from raw call hex, or json with module and method by index or name
+pub fn parse_call(input: &str, call_names: &CallNames) -> Result<Call, String> {
+	let input = input.trim();
+
+	if input.starts_with("0x") {
+		let raw: Vec<u8> = Hex::from_str(input)?.into();
+		let call: Call = Decode::decode(&mut &raw[..]).ok_or("Invalid call")?;
+		if call.encode() != raw {
+			return Err("Invalid call".to_string());
+		}
+		return Ok(call);
+	}
+
+	let mut value: Value = serde_json::from_str(input).map_err(|_| "Invalid call json")?;
+
+	if let Some(Value::String(module)) = value.get("module") {
+		let module = call_names.parse_module(module)?;
+		value["module"] = module.into();
+	}
+
+	let module = value
+		.get("module")
+		.and_then(Value::as_u64)
+		.ok_or("Invalid module")? as u8;
+
+	if let Some(Value::String(method)) = value.get("method") {
+		let method = call_names.parse_method(module, method)?;
+		value["method"] = method.into();
+	}
+
+	Ok(build_call(value.to_string().as_bytes())?)
+}
+
+/// Build call from the module, the method and the encoded params, only if encoded back to the same bytes
+pub fn build_raw_call<P: Encode>(
+	call_names: &CallNames,
+	module: &str,
+	method: &str,
+	params: &P,
+) -> Result<Call, String> {
+	decode_raw_call(call_names, module, method, params.encode())
+}
+
+/// Call of the module and method by name, with the params encoded
+fn decode_raw_call(
+	call_names: &CallNames,
+	module: &str,
+	method: &str,
+	params: Vec<u8>,
+) -> Result<Call, String> {
+	let module = call_names.parse_module(module)?;
+	let mut raw = vec![module, call_names.parse_method(module, method)?];
+	raw.extend(params);
+	let call: Call = Decode::decode(&mut &raw[..]).ok_or(format!("Invalid {} call", method))?;
+	if call.encode() != raw {
+		return Err(format!("Invalid {} call", method));
+	}
+	Ok(call)
+}
+
+fn build_sudo_call(proposal: Call) -> Call {
+	Call::Sudo(call::sudo::Call::Sudo(call::sudo::Sudo {
+		proposal: Box::new(proposal),
+	}))
+}
+
+/// Compose tx of the call, signed by the keystore or the agent
+pub fn compose_call(matches: &ArgMatches, call: Call) -> Result<ComposeResult, String> {
+	let signer = get_signer(matches)?;
+
+	compose_call_by(matches, signer, call)
+}
+
+/// Signer by the keystore agent, or the keystore (may prompt for the password)
+fn get_signer<'a>(matches: &'a ArgMatches) -> Result<Signer<'a>, String> {
+	let agent = matches.value_of("AGENT");
+
+	let keystore_path = match agent {
+		Some(_) => None,
+		None => Some(base::get_keystore_path(matches)?),
+	};
+
+	let signer = match (agent, keystore_path) {
+		(Some(agent), _) => {
+			let public_key = match agent::agent_list(agent)?.as_slice() {
+				[public_key] if public_key.len() == PUBLIC_KEY_LEN => {
+					let mut tmp = [0u8; PUBLIC_KEY_LEN];
+					tmp.copy_from_slice(&public_key);
+					tmp
+				}
+				[_] => return Err("Invalid public key from the agent".to_string()),
+				[] => return Err("No key loaded in the agent".to_string()),
+				_ => return Err("More than one key loaded in the agent".to_string()),
+			};
+			Signer::Agent(agent, public_key)
+		}
+		(None, Some(keystore_path)) => {
+			let secret_key = get_secret_key(&keystore_path)?;
+			Signer::SecretKey(secret_key)
+		}
+		(None, None) => return Err("Keystore path or agent required".to_string()),
+	};
+
+	Ok(signer)
+}
+
+fn compose_call_by(
+	matches: &ArgMatches,
+	signer: Signer,
+	call: Call,
+) -> Result<ComposeResult, String> {
+	let period = match matches.value_of("PERIOD") {
+		Some(period) => period.parse::<u64>().map_err(|_| "Invalid period")?,
+		None => 64,
+	};
+
+	let public_key = signer.public_key()?;
+
+	let rpc = &base::get_rpc_by_public_key(matches, &public_key)?;
 
 	let block_info = get_block_info(Number::Best, rpc)?;
 	let (best_number, best_hash, shard_info) = (
@@ -245,32 +888,14 @@ fn compose(matches: &ArgMatches) -> Result<Vec<String>, String> {
 
 	let (shard_num, shard_count) = shard_info.ok_or("Invalid shard info".to_string())?;
 
-	let secret_key = if keystore_path.starts_with("0x") {
-		let mut secret_key = match hex::decode(keystore_path.trim_start_matches("0x")) {
-			Ok(v) => v,
-			Err(_) => return Err("Invalid hex secret key".to_string()),
-		};
-		if secret_key.len() == 32 {
-			let key_pair = KeyPair::from_mini_secret_key(&secret_key.clone())?;
-			secret_key = key_pair.secret_key().to_vec();
-		}
-		if secret_key.len() != 64 {
-			return Err("Invalid hex secret key length".to_string());
-		}
-		secret_key
-	} else {
-		get_keystore(keystore_path)?
-	};
-
-	let key_pair = KeyPair::from_secret_key(&secret_key)?;
-
-	let public_key = key_pair.public_key();
-
 	let shard_num_for_public_key =
 		utils::shard_num_for_bytes(&public_key, shard_count).expect("qed");
 
 	if shard_num_for_public_key != shard_num {
-		return Err("the shard number of the secret key and the node not match".to_string());
+		return Err(format!(
+			"the shard number of the secret key ({}) and the node ({}) not match",
+			shard_num_for_public_key, shard_num
+		));
 	}
 
 	let nonce = match matches.value_of("NONCE") {
@@ -278,14 +903,6 @@ fn compose(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		None => get_nonce(public_key, rpc)?,
 	};
 
-	let call = build_call(call.as_bytes())?;
-
-	let secret_key = {
-		let mut tmp = [0u8; SECRET_KEY_LEN];
-		tmp.copy_from_slice(&secret_key);
-		tmp
-	};
-
 	let current = best_number;
 	let current_hash = {
 		let mut tmp = [0u8; HASH_LEN];
@@ -293,7 +910,22 @@ fn compose(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		tmp
 	};
 
-	let tx = build_tx(secret_key, nonce, period, current, current_hash, call)?;
+	let tx = match signer {
+		Signer::SecretKey(secret_key) => {
+			let secret_key = {
+				let mut tmp = [0u8; SECRET_KEY_LEN];
+				tmp.copy_from_slice(&secret_key);
+				tmp
+			};
+			build_tx(secret_key, nonce, period, current, current_hash, call)?
+		}
+		Signer::Agent(agent, public_key) => {
+			let era = Era::mortal(period, current);
+			let payload = signing_payload(nonce, &call, era, current_hash);
+			let signature = agent::agent_sign(agent, &public_key, &payload)?;
+			assemble_tx(public_key, signature, nonce, era, call)?
+		}
+	};
 	let raw = tx.encode();
 
 	let result = ComposeResult {
@@ -314,14 +946,80 @@ fn compose(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		raw: raw.into(),
 	};
 
-	base::output(result)
+	Ok(result)
+}
+
+enum Signer<'a> {
+	SecretKey(Vec<u8>),
+	Agent(&'a str, [u8; PUBLIC_KEY_LEN]),
+}
+
+impl<'a> Signer<'a> {
+	fn public_key(&self) -> Result<[u8; PUBLIC_KEY_LEN], String> {
+		match self {
+			Signer::Agent(_, public_key) => Ok(public_key.clone()),
+			Signer::SecretKey(secret_key) => Ok(KeyPair::from_secret_key(secret_key)?.public_key()),
+		}
+	}
+}
+
+/// Get secret key from keystore file, or hex secret key (or mini secret key) directly
+fn get_secret_key(keystore_path: &str) -> Result<Vec<u8>, String> {
+	let secret_key = if keystore_path.starts_with("0x") {
+		let mut secret_key = match hex::decode(keystore_path.trim_start_matches("0x")) {
+			Ok(v) => v,
+			Err(_) => return Err("Invalid hex secret key".to_string()),
+		};
+		if secret_key.len() == 32 {
+			let key_pair = KeyPair::from_mini_secret_key(&secret_key.clone())?;
+			secret_key = key_pair.secret_key().to_vec();
+		}
+		if secret_key.len() != 64 {
+			return Err("Invalid hex secret key length".to_string());
+		}
+		secret_key
+	} else {
+		get_keystore(keystore_path)?
+	};
+	Ok(secret_key)
+}
+
+/// The payload to be signed, the same as build_tx
+pub fn signing_payload(nonce: u64, call: &Call, era: Era, current_hash: [u8; HASH_LEN]) -> Vec<u8> {
+	let payload = (Compact(nonce), call, era, current_hash).encode();
+	if payload.len() > 256 {
+		blake2_256(&payload).to_vec()
+	} else {
+		payload
+	}
+}
+
+/// Assemble a signed tx with a signature made outside
+fn assemble_tx(
+	public_key: [u8; PUBLIC_KEY_LEN],
+	signature: Vec<u8>,
+	nonce: u64,
+	era: Era,
+	call: Call,
+) -> Result<Transaction, String> {
+	let mut raw = vec![];
+	let mut sender = vec![0xffu8];
+	sender.extend_from_slice(&public_key);
+	raw.extend(sender);
+	raw.extend(signature);
+	raw.extend(Compact(nonce).encode());
+	raw.extend(era.encode());
+	let signature = Decode::decode(&mut &raw[..]).ok_or("Invalid signature")?;
+
+	Ok(Transaction {
+		signature: Some(signature),
+		call,
+	})
 }
 
 fn submit(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	let input = base::input_string(matches)?;
 
-	let rpc = &get_rpc(matches);
-
 	let try_get_raw_from_hex = |input: String| -> Result<String, String> {
 		let _raw = Hex::from_str(&input)?;
 		Ok(input)
@@ -345,7 +1043,12 @@ fn submit(matches: &ArgMatches) -> Result<Vec<String>, String> {
 	// verify raw
 	let raw1 = Hex::from_str(&raw)?;
 	let raw1: Vec<u8> = raw1.into();
-	let _tx: Transaction = Decode::decode(&mut &raw1[..]).ok_or("Invalid tx")?;
+	let tx: Transaction = Decode::decode(&mut &raw1[..]).ok_or("Invalid tx")?;
+
+	let rpc = &match tx.signature {
+		Some((account, ..)) => base::get_rpc_by_public_key(matches, &account.0[1..])?,
+		None => get_rpc(matches)?,
+	};
 
 	// submit
 	let mut runtime = Runtime::new().expect("qed");
@@ -363,7 +1066,7 @@ fn submit(matches: &ArgMatches) -> Result<Vec<String>, String> {
 }
 
 fn search(matches: &ArgMatches) -> Result<Vec<String>, String> {
-	let rpc = &get_rpc(matches);
+	let rpc = &get_rpc(matches)?;
 
 	let hrp = get_hrp(rpc)?;
 	let block_info = get_block_info(Number::Best, rpc)?;
@@ -375,31 +1078,7 @@ fn search(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		.map_err(|_| "Failed to set shard_count")?;
 	HRP.set(hrp).map_err(|_| "Failed to set hrp")?;
 
-	let expected_hash: Option<Vec<u8>> = match matches.value_of("HASH") {
-		Some(v) => {
-			let tmp = Hex::from_str(v)?;
-			Some(tmp.into())
-		}
-		None => None,
-	};
-
-	let expected_raw: Option<Vec<u8>> = match matches.value_of("RAW") {
-		Some(v) => {
-			let tmp = Hex::from_str(v)?;
-			Some(tmp.into())
-		}
-		None => None,
-	};
-
-	let expected_sender: Option<Vec<u8>> = match matches.value_of("SENDER") {
-		Some(v) => {
-			let tmp = Hex::from_str(v)?;
-			Some(tmp.into())
-		}
-		None => None,
-	};
-
-	let include_inherent: bool = matches.is_present("INCLUDE_INHERENT");
+	let filter = get_search_filter(matches)?;
 
 	let from: BlockNumber = match matches.value_of("FROM_BLOCK_NUMBER") {
 		Some(v) => match v {
@@ -491,13 +1170,7 @@ fn search(matches: &ArgMatches) -> Result<Vec<String>, String> {
 				let success = result.map(|x| x.0);
 				let events = result.map(|x| x.1.clone());
 				let item = build_search_item(raw, block, success, events)?;
-				let accept = accept_item(
-					&item,
-					expected_hash.as_ref(),
-					expected_raw.as_ref(),
-					expected_sender.as_ref(),
-					include_inherent,
-				);
+				let accept = filter.accept(&item);
 
 				if accept {
 					items.push(item);
@@ -511,45 +1184,364 @@ fn search(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		let extrinsics = get_pending_extrinsics(rpc)?;
 		for raw in extrinsics {
 			let item = build_search_item(raw, SearchItemBlock::Pending, None, None)?;
-			let accept = accept_item(
-				&item,
-				expected_hash.as_ref(),
-				expected_raw.as_ref(),
-				expected_sender.as_ref(),
-				include_inherent,
-			);
+			let accept = filter.accept(&item);
+
+			if accept {
+				items.push(item);
+			}
+		}
+	}
+
+	// append in waiting
+	if search_in_waiting {
+		let extrinsics = get_waiting_extrinsics(rpc)?;
+		for raw in extrinsics {
+			let item = build_search_item(raw, SearchItemBlock::Waiting, None, None)?;
+			let accept = filter.accept(&item);
 
 			if accept {
 				items.push(item);
 			}
 		}
-	}
+	}
+
+	let shard_rpc_map = base::get_shard_rpc_map(matches)?;
+	let mut relay_verifier = RelayVerifier::new(&shard_rpc_map);
+
+	let result = items
+		.into_iter()
+		.map(|item| {
+			let relay_signature_valid = relay_verifier.verify(&item.tx);
+			let mut item: SerdeSearchItem = item.into();
+			set_relay_signature_valid(&mut item.tx, relay_signature_valid);
+			item
+		})
+		.collect::<Vec<SerdeSearchItem>>();
+
+	base::output(&result)
+}
+
+fn track(matches: &ArgMatches) -> Result<Vec<String>, String> {
+	let hash: Vec<u8> = Hex::from_str(matches.value_of("HASH").expect("qed"))?.into();
+
+	let blocks = match matches.value_of("BLOCKS") {
+		Some(blocks) => blocks.parse::<u64>().map_err(|_| "Invalid blocks")?,
+		None => 100,
+	};
+
+	let shard_rpc_map = base::get_shard_rpc_map(matches)?;
+	if shard_rpc_map.is_empty() {
+		return Err("Shard RPC address required: use --shard-rpc or a profile".to_string());
+	}
+
+	// origin leg
+	let mut origin = None;
+	for rpc in shard_rpc_map.values() {
+		origin = find_extrinsic(rpc, blocks, None, true, |x, _| x == hash.as_slice())?;
+		if origin.is_some() {
+			break;
+		}
+	}
+	let (origin, origin_tx) = origin.ok_or("TX not found in any shard")?;
+
+	let dest = match &origin_tx.call {
+		Call::Balances(call::balances::Call::Transfer(transfer)) => {
+			Some((transfer.dest.0)[1..].to_vec())
+		}
+		_ => None,
+	};
+	let dest_shard_num = match dest {
+		Some(dest) => Some(
+			shard_num_for_bytes(&dest, origin.shard.shard_count).ok_or("Invalid shard count")?,
+		),
+		None => None,
+	};
+	let cross_shard = match dest_shard_num {
+		Some(dest_shard_num) => dest_shard_num != origin.shard.shard_num,
+		None => false,
+	};
+
+	// destination leg
+	let destination = match (dest_shard_num, cross_shard, origin.success) {
+		(Some(dest_shard_num), true, Some(true)) => {
+			let rpc = shard_rpc_map
+				.get(&dest_shard_num)
+				.ok_or(format!("No rpc for shard {}", dest_shard_num))?;
+			find_extrinsic(rpc, blocks, origin.timestamp, false, |_, tx| {
+				relay_transfer(&tx.call)
+					.map(|x| x.is_relay_of(&hash))
+					.unwrap_or(false)
+			})?
+			.map(|x| x.0)
+		}
+		_ => None,
+	};
+
+	let latency = match (&destination, origin.timestamp) {
+		(Some(destination), Some(origin_timestamp)) => destination
+			.timestamp
+			.map(|x| x.saturating_sub(origin_timestamp)),
+		_ => None,
+	};
+
+	let status = match (origin.success, cross_shard, &destination) {
+		(None, ..) => TrackStatus::Pending,
+		(Some(false), ..) => TrackStatus::Failed,
+		(Some(true), false, _) => TrackStatus::Completed,
+		(Some(true), true, None) => TrackStatus::Relaying,
+		(Some(true), true, Some(destination)) => match destination.success {
+			Some(true) => TrackStatus::Completed,
+			_ => TrackStatus::RelayFailed,
+		},
+	};
+
+	#[derive(Serialize)]
+	struct Output {
+		hash: Hex,
+		status: TrackStatus,
+		cross_shard: bool,
+		origin: TrackLeg,
+		destination: Option<TrackLeg>,
+		latency: Option<u64>,
+	}
+
+	let output = Output {
+		hash: hash.into(),
+		status,
+		cross_shard,
+		origin,
+		destination,
+		latency,
+	};
+
+	base::output(output)
+}
+
+/// Find extrinsic in the pool (optional) and backward from the best block,
+/// stop after `blocks` blocks or at the block earlier than `since` (timestamp)
+fn find_extrinsic<F>(
+	rpc: &str,
+	blocks: u64,
+	since: Option<u64>,
+	include_pool: bool,
+	f: F,
+) -> Result<Option<(TrackLeg, Transaction)>, String>
+where
+	F: Fn(&[u8], &Transaction) -> bool,
+{
+	let block_info = get_block_info(Number::Best, rpc)?;
+	let shard = block_info.shard.ok_or("Invalid shard info")?;
+	let shard = Shard {
+		shard_num: shard.shard_num,
+		shard_count: shard.shard_count,
+	};
+
+	let decode = |raw: &[u8]| -> Option<(Vec<u8>, Transaction)> {
+		let tx: Transaction = Decode::decode(&mut &raw[..])?;
+		Some((blake2_256(raw).to_vec(), tx))
+	};
+
+	if include_pool {
+		let pool = vec![
+			(get_waiting_extrinsics(rpc)?, SearchItemBlock::Waiting),
+			(get_pending_extrinsics(rpc)?, SearchItemBlock::Pending),
+		];
+		for (extrinsics, block) in pool {
+			for raw in extrinsics {
+				if let Some((hash, tx)) = decode(&raw) {
+					if f(&hash, &tx) {
+						let leg = TrackLeg {
+							shard: shard.clone(),
+							hash: hash.into(),
+							block: block.into(),
+							timestamp: None,
+							success: None,
+							events: None,
+						};
+						return Ok(Some((leg, tx)));
+					}
+				}
+			}
+		}
+	}
+
+	let best_number = block_info.number;
+	let from = (best_number + 1).saturating_sub(blocks);
+	for number in (from..=best_number).rev() {
+		let block_info = get_block_info(Number::Number(number), rpc)?;
+		let timestamp = block_info.pow.as_ref().map(|x| x.timestamp);
+		if let (Some(since), Some(timestamp)) = (since, timestamp) {
+			if timestamp < since {
+				break;
+			}
+		}
+		let block_hash: Vec<u8> = block_info.hash.into();
+		let extrinsics = get_block_extrinsics(rpc, &block_hash)?;
+		for (index, raw) in extrinsics.into_iter().enumerate() {
+			if let Some((hash, tx)) = decode(&raw) {
+				if f(&hash, &tx) {
+					let results = get_block_extrinsics_result(rpc, &block_hash)?;
+					let result = results.get(&(index as u32));
+					let leg = TrackLeg {
+						shard: shard.clone(),
+						hash: hash.into(),
+						block: SerdeSearchItemBlock::Number {
+							number,
+							hash: block_hash.clone().into(),
+							index: index as u32,
+						},
+						timestamp,
+						success: result.map(|x| x.0),
+						events: result.map(|x| x.1.clone()),
+					};
+					return Ok(Some((leg, tx)));
+				}
+			}
+		}
+	}
+
+	Ok(None)
+}
+
+/// Verify the signature of the origin tx of relay, by the block hash of the era birth in the origin shard.
+/// The shard count and the checkpoint hashes are cached, so a list of txs costs few rpc calls.
+struct RelayVerifier<'a> {
+	shard_rpc_map: &'a BTreeMap<u16, String>,
+	shard_count: Option<u16>,
+	checkpoint_hashes: HashMap<(u16, u64), [u8; HASH_LEN]>,
+}
+
+impl<'a> RelayVerifier<'a> {
+	fn new(shard_rpc_map: &'a BTreeMap<u16, String>) -> Self {
+		RelayVerifier {
+			shard_rpc_map,
+			shard_count: None,
+			checkpoint_hashes: HashMap::new(),
+		}
+	}
+
+	/// None if not a relay tx or not able to verify (e.g. no rpc of the origin shard),
+	/// Some(false) if the origin tx is invalid or unsigned
+	fn verify(&mut self, tx: &Transaction) -> Option<bool> {
+		let transfer = relay_transfer(&tx.call)?;
+		let origin_tx = match transfer.origin_tx() {
+			Some(origin_tx) => origin_tx,
+			None => return Some(false),
+		};
+		let (account, signature, nonce, era) = match origin_tx.signature.as_ref() {
+			Some(signature) => signature,
+			None => return Some(false),
+		};
+		let public_key = &account.0[1..];
+
+		let (era, birth) = match era {
+			Era::Immortal => (Era::Immortal, 0),
+			Era::Mortal(period, phase) => (
+				Era::Mortal(*period, *phase),
+				era_birth(*period, *phase, transfer.number.0),
+			),
+		};
+		let current_hash = self.get_checkpoint_hash(public_key, birth).ok()?;
+
+		let payload = signing_payload(nonce.0, &origin_tx.call, era, current_hash);
+
+		let valid = verify_signature(public_key, &payload, &signature.to_vec()).unwrap_or(false);
+
+		Some(valid)
+	}
+
+	fn get_shard_count(&mut self) -> Result<u16, String> {
+		if let Some(shard_count) = self.shard_count {
+			return Ok(shard_count);
+		}
+		let any_rpc = self.shard_rpc_map.values().next().ok_or("No shard rpc")?;
+		let shard_count = get_block_info(Number::Best, any_rpc)?
+			.shard
+			.ok_or("Invalid shard info")?
+			.shard_count;
+		self.shard_count = Some(shard_count);
+		Ok(shard_count)
+	}
+
+	fn get_checkpoint_hash(
+		&mut self,
+		public_key: &[u8],
+		birth: u64,
+	) -> Result<[u8; HASH_LEN], String> {
+		let shard_count = self.get_shard_count()?;
+		let shard_num =
+			shard_num_for_bytes(public_key, shard_count).ok_or("Invalid shard count")?;
+		if let Some(hash) = self.checkpoint_hashes.get(&(shard_num, birth)) {
+			return Ok(*hash);
+		}
+		let rpc = self
+			.shard_rpc_map
+			.get(&shard_num)
+			.ok_or(format!("No rpc for shard {}", shard_num))?;
+		let hash = get_checkpoint_hash(rpc, birth)?;
+		self.checkpoint_hashes.insert((shard_num, birth), hash);
+		Ok(hash)
+	}
+}
+
+/// The block hash of the era birth, signed in the payload as the checkpoint
+fn get_checkpoint_hash(rpc: &str, birth: u64) -> Result<[u8; HASH_LEN], String> {
+	let birth_hash: Vec<u8> = get_block_info(Number::Number(birth), rpc)?.hash.into();
+	to_hash(&birth_hash)
+}
+
+fn to_hash(input: &[u8]) -> Result<[u8; HASH_LEN], String> {
+	if input.len() != HASH_LEN {
+		return Err("Invalid block hash".to_string());
+	}
+	let mut hash = [0u8; HASH_LEN];
+	hash.copy_from_slice(input);
+	Ok(hash)
+}
+
+/// Eras before the birth at the number to search for the birth of an expired tx
+const EXPIRED_ERAS: u64 = 8;
+
+/// The era births to verify the signature by, the birth at the number first and then the older ones:
+/// a tx only verified by an older birth is signed in an expired era
+fn era_births(era: &Era, number: u64) -> Result<Vec<u64>, String> {
+	let births = match era {
+		Era::Immortal => vec![0],
+		Era::Mortal(period, phase) => {
+			let birth = era_birth(*period, *phase, number);
+			if birth > number {
+				return Err("Era not born at the number".to_string());
+			}
+			(0..=EXPIRED_ERAS)
+				.filter_map(|i| birth.checked_sub(i * period))
+				.collect()
+		}
+	};
+	Ok(births)
+}
 
-	// append in waiting
-	if search_in_waiting {
-		let extrinsics = get_waiting_extrinsics(rpc)?;
-		for raw in extrinsics {
-			let item = build_search_item(raw, SearchItemBlock::Waiting, None, None)?;
-			let accept = accept_item(
-				&item,
-				expected_hash.as_ref(),
-				expected_raw.as_ref(),
-				expected_sender.as_ref(),
-				include_inherent,
-			);
+/// The block number of the era birth, the same as substrate Era::birth
+fn era_birth(period: u64, phase: u64, current: u64) -> u64 {
+	(max(current, phase) - phase) / period * period + phase
+}
 
-			if accept {
-				items.push(item);
+fn set_relay_signature_valid(tx: &mut SerdeTransaction, valid: Option<bool>) {
+	if let SerdeCall::Relay(Decoded::Typed(relay::SerdeCall::Transfer(transfer))) = &mut tx.call {
+		transfer.signature_valid = valid;
+	}
+}
+
+/// Decode relay transfer from the relay call
+fn relay_transfer(call: &Call) -> Option<relay::Transfer> {
+	match call {
+		Call::Relay(call) => {
+			let call: relay::Call = decode_call(call)?;
+			match call {
+				relay::Call::Transfer(transfer) => Some(transfer),
 			}
 		}
+		_ => None,
 	}
-
-	let result = items
-		.into_iter()
-		.map(Into::into)
-		.collect::<Vec<SerdeSearchItem>>();
-
-	base::output(&result)
 }
 
 fn make_set_code_call(matches: &ArgMatches) -> Result<Vec<String>, String> {
@@ -563,9 +1555,7 @@ fn make_set_code_call(matches: &ArgMatches) -> Result<Vec<String>, String> {
 		new: Bytes(code),
 	}));
 
-	let call = Call::Sudo(call::sudo::Call::Sudo(call::sudo::Sudo {
-		proposal: Box::new(set_code_call),
-	}));
+	let call = build_sudo_call(set_code_call);
 
 	let call_json = serde_json::to_vec(&call).map_err(|_| "Encode error")?;
 
@@ -579,8 +1569,52 @@ lazy_static! {
 	pub static ref SHARD_COUNT: MutStatic<u16> = MutStatic::new();
 }
 
+#[derive(Serialize)]
+struct VerifyResult {
+	sender_address: String,
+	nonce: u64,
+	checkpoint_hash: Hex,
+	signature_valid: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	check: Option<TxCheck>,
+	valid: bool,
+}
+
+/// The tx checked against the node
+#[derive(Serialize)]
+struct TxCheck {
+	era_valid: bool,
+	nonce_valid: bool,
+	account_nonce: u64,
+	/// The free balance covers the transfer value, the fee not counted; None if not a transfer
+	value_enough: Option<bool>,
+	free_balance: u128,
+	shard_match: bool,
+	node_shard: Shard,
+}
+
+impl TxCheck {
+	fn valid(&self) -> bool {
+		self.era_valid && self.nonce_valid && self.value_enough != Some(false) && self.shard_match
+	}
+}
+
+/// Composed tx with the decoded call
+#[derive(Serialize)]
+pub struct ComposeOutput {
+	#[serde(flatten)]
+	pub compose: ComposeResult,
+	pub call: SerdeCall,
+}
+
+#[derive(Serialize)]
+pub struct CallPreview {
+	pub call: SerdeCall,
+	pub raw: Hex,
+}
+
 #[derive(Serialize, Deserialize)]
-struct ComposeResult {
+pub struct ComposeResult {
 	shard_num: u16,
 	shard_count: u16,
 	sender_address: String,
@@ -659,6 +1693,25 @@ impl From<SearchItemBlock> for SerdeSearchItemBlock {
 	}
 }
 
+#[derive(Serialize)]
+struct TrackLeg {
+	shard: Shard,
+	hash: Hex,
+	block: SerdeSearchItemBlock,
+	timestamp: Option<u64>,
+	success: Option<bool>,
+	events: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+enum TrackStatus {
+	Pending,
+	Failed,
+	Relaying,
+	RelayFailed,
+	Completed,
+}
+
 #[derive(Serialize, Debug, Copy, Clone)]
 enum BlockNumber {
 	Number(u64),
@@ -667,7 +1720,7 @@ enum BlockNumber {
 }
 
 #[derive(Serialize)]
-struct SerdeSignature {
+pub struct SerdeSignature {
 	pub sender: Hex,
 	pub sender_address: String,
 	pub sender_testnet_address: String,
@@ -678,7 +1731,7 @@ struct SerdeSignature {
 }
 
 #[derive(Serialize)]
-struct SerdeTransaction {
+pub struct SerdeTransaction {
 	pub signature: Option<SerdeSignature>,
 	pub call: SerdeCall,
 }
@@ -693,10 +1746,18 @@ pub enum SerdeCall {
 	Sharding(call::sharding::Call),
 	Crfg(call::crfg::Call),
 	FinalityTracker(call::finality_tracker::Call),
-	Assets(call::assets::Call),
-	Relay(call::relay::Call),
+	Assets(Decoded<assets::SerdeCall, call::assets::Call>),
+	Relay(Decoded<relay::SerdeCall, call::relay::Call>),
 	Storage(call::storage::Call),
-	Sudo(call::sudo::Call),
+	Sudo(Decoded<sudo::SerdeCall, call::sudo::Call>),
+}
+
+/// Typed call if decoded by the hack module, or the call as is
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Decoded<T, R> {
+	Typed(T),
+	Raw(R),
 }
 
 #[derive(Serialize)]
@@ -716,14 +1777,51 @@ impl From<Call> for SerdeCall {
 			Call::Sharding(call) => SerdeCall::Sharding(call),
 			Call::Crfg(call) => SerdeCall::Crfg(call),
 			Call::FinalityTracker(call) => SerdeCall::FinalityTracker(call),
-			Call::Assets(call) => SerdeCall::Assets(call),
-			Call::Relay(call) => SerdeCall::Relay(call),
+			Call::Assets(call) => SerdeCall::Assets(typed::<_, assets::Call, _>(call)),
+			Call::Relay(call) => SerdeCall::Relay(typed::<_, relay::Call, _>(call)),
 			Call::Storage(call) => SerdeCall::Storage(call),
-			Call::Sudo(call) => SerdeCall::Sudo(call),
+			Call::Sudo(call) => SerdeCall::Sudo(typed::<_, sudo::Call, _>(call)),
 		}
 	}
 }
 
+fn typed<R: Encode, M: Encode + Decode + Into<T>, T>(call: R) -> Decoded<T, R> {
+	match decode_call::<_, M>(&call) {
+		Some(call) => Decoded::Typed(call.into()),
+		None => Decoded::Raw(call),
+	}
+}
+
+/// Decode call into the hack module type, only if encoded back to the same bytes
+fn decode_call<F: Encode, T: Encode + Decode>(call: &F) -> Option<T> {
+	let raw = call.encode();
+	let t: T = Decode::decode(&mut &raw[..])?;
+	if t.encode() != raw {
+		return None;
+	}
+	Some(t)
+}
+
+/// Address, testnet address and shard list of the account
+fn account_info(account: &Account) -> (String, String, Vec<Shard>) {
+	let public = (account.0)[1..].to_vec();
+	let address = public.to_address(Hrp::MAINNET).expect("qed").0;
+	let testnet_address = public.to_address(Hrp::TESTNET).expect("qed").0;
+	// only for display in the decoded call, no shards shown if the profile is malformed
+	let shard = shard_count_list()
+		.unwrap_or_default()
+		.into_iter()
+		.map(|shard_count| {
+			let shard_num = shard_num_for_bytes(public.as_slice(), shard_count).expect("qed");
+			Shard {
+				shard_num,
+				shard_count,
+			}
+		})
+		.collect::<Vec<_>>();
+	(address, testnet_address, shard)
+}
+
 // hack balance
 mod balances {
 	use crate::modules::account::Shard;
@@ -746,125 +1844,676 @@ mod balances {
 	}
 }
 
-impl From<call::balances::Call> for balances::Call {
-	fn from(t: call::balances::Call) -> Self {
-		match t {
-			call::balances::Call::Transfer(transfer) => balances::Call::Transfer(transfer.into()),
-		}
+// hack assets
+pub mod assets {
+	use crate::modules::account::Shard;
+	use crate::modules::base::Hex;
+
+	use super::Account;
+	use super::{Compact, Decode, Encode, Serialize};
+
+	#[derive(Encode, Decode)]
+	pub enum Call {
+		Issue(Issue),
+		Transfer(Transfer),
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct Issue {
+		pub name: Vec<u8>,
+		pub total: Compact<u128>,
+		pub decimals: u16,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct Transfer {
+		pub shard_code: Vec<u8>,
+		pub id: Compact<u32>,
+		pub target: Account,
+		pub amount: Compact<u128>,
+	}
+
+	#[derive(Serialize)]
+	pub enum SerdeCall {
+		Issue(SerdeIssue),
+		Transfer(SerdeTransfer),
+	}
+
+	#[derive(Serialize)]
+	pub struct SerdeIssue {
+		pub name: String,
+		pub total: u128,
+		pub decimals: u16,
+	}
+
+	#[derive(Serialize)]
+	pub struct SerdeTransfer {
+		pub shard_code: Hex,
+		pub id: u32,
+		pub target: Account,
+		pub target_address: String,
+		pub target_testnet_address: String,
+		pub target_shard: Vec<Shard>,
+		pub amount: u128,
+	}
+}
+
+// hack relay
+mod relay {
+	use substrate_primitives::blake2_256;
+
+	use crate::modules::account::Shard;
+	use crate::modules::base::Hex;
+
+	use super::{Compact, Decode, Encode, SerdeTransaction, Serialize, Transaction};
+
+	#[derive(Encode, Decode, Clone, Debug)]
+	pub enum Call {
+		Transfer(Transfer),
+	}
+
+	#[derive(Encode, Decode, Clone, Debug)]
+	pub struct Transfer {
+		pub relay_type: RelayTypes,
+		pub tx: Vec<u8>,
+		pub number: Compact<u64>,
+		pub hash: [u8; 32],
+		pub parent: [u8; 32],
+	}
+
+	#[derive(Encode, Decode, Clone, Debug, Serialize)]
+	pub enum RelayTypes {
+		Balance,
+		Assets,
+	}
+
+	#[derive(Serialize)]
+	pub enum SerdeCall {
+		Transfer(SerdeTransfer),
+	}
+
+	#[derive(Serialize)]
+	pub struct SerdeTransfer {
+		pub relay_type: RelayTypes,
+		pub tx: Hex,
+		pub origin_tx: Option<Box<SerdeTransaction>>,
+		pub origin_tx_hash: Hex,
+		pub origin_shard: Vec<Shard>,
+		pub origin_block_number: u64,
+		pub origin_block_hash: Hex,
+		pub origin_parent_hash: Hex,
+		pub signature_valid: Option<bool>,
+	}
+
+	impl Transfer {
+		/// The hash of the origin tx, the same as in the origin shard
+		pub fn origin_tx_hash(&self) -> Vec<u8> {
+			let with_prefix = Decode::decode(&mut &self.tx[..])
+				.map(|_: Transaction| true)
+				.unwrap_or(false);
+			if with_prefix {
+				blake2_256(&self.tx).to_vec()
+			} else {
+				let mut raw = Compact(self.tx.len() as u32).encode();
+				raw.extend_from_slice(&self.tx);
+				blake2_256(&raw).to_vec()
+			}
+		}
+
+		/// Decode the origin tx (with or without length prefix)
+		pub fn origin_tx(&self) -> Option<Transaction> {
+			let mut raw = Compact(self.tx.len() as u32).encode();
+			raw.extend_from_slice(&self.tx);
+			Decode::decode(&mut &self.tx[..]).or_else(|| Decode::decode(&mut &raw[..]))
+		}
+
+		/// Whether the relay carries the origin tx of the hash (with or without length prefix)
+		pub fn is_relay_of(&self, hash: &[u8]) -> bool {
+			let mut raw = Compact(self.tx.len() as u32).encode();
+			raw.extend_from_slice(&self.tx);
+			blake2_256(&self.tx)[..] == *hash || blake2_256(&raw)[..] == *hash
+		}
+	}
+}
+
+// hack sudo
+mod sudo {
+	use crate::modules::account::Shard;
+
+	use super::{Account, Decode, Encode, Serialize};
+
+	#[derive(Encode, Decode)]
+	pub enum Call {
+		Sudo(Sudo),
+		SetKey(SetKey),
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct Sudo {
+		pub proposal: Box<super::Call>,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct SetKey {
+		pub new: Account,
+	}
+
+	#[derive(Serialize)]
+	pub enum SerdeCall {
+		Sudo(SerdeSudo),
+		SetKey(SerdeSetKey),
+	}
+
+	#[derive(Serialize)]
+	pub struct SerdeSudo {
+		pub proposal: Box<super::SerdeCall>,
+	}
+
+	#[derive(Serialize)]
+	pub struct SerdeSetKey {
+		pub new: Account,
+		pub new_address: String,
+		pub new_testnet_address: String,
+		pub new_shard: Vec<Shard>,
+	}
+}
+
+impl From<call::balances::Call> for balances::Call {
+	fn from(t: call::balances::Call) -> Self {
+		match t {
+			call::balances::Call::Transfer(transfer) => balances::Call::Transfer(transfer.into()),
+		}
+	}
+}
+
+impl From<call::balances::Transfer> for balances::Transfer {
+	fn from(t: call::balances::Transfer) -> Self {
+		let (address, testnet_address, shard) = account_info(&t.dest);
+		balances::Transfer {
+			dest: t.dest,
+			value: t.value,
+			dest_address: address,
+			dest_testnet_address: testnet_address,
+			dest_shard: shard,
+		}
+	}
+}
+
+impl From<assets::Call> for assets::SerdeCall {
+	fn from(t: assets::Call) -> Self {
+		match t {
+			assets::Call::Issue(issue) => assets::SerdeCall::Issue(assets::SerdeIssue {
+				name: String::from_utf8_lossy(&issue.name).to_string(),
+				total: issue.total.0,
+				decimals: issue.decimals,
+			}),
+			assets::Call::Transfer(transfer) => {
+				let (address, testnet_address, shard) = account_info(&transfer.target);
+				assets::SerdeCall::Transfer(assets::SerdeTransfer {
+					shard_code: transfer.shard_code.into(),
+					id: transfer.id.0,
+					target: transfer.target,
+					target_address: address,
+					target_testnet_address: testnet_address,
+					target_shard: shard,
+					amount: transfer.amount.0,
+				})
+			}
+		}
+	}
+}
+
+impl From<relay::Call> for relay::SerdeCall {
+	fn from(t: relay::Call) -> Self {
+		match t {
+			relay::Call::Transfer(transfer) => {
+				let origin_tx = transfer.origin_tx();
+				let origin_shard = origin_tx
+					.as_ref()
+					.and_then(|x| x.signature.as_ref())
+					.map(|(account, ..)| account_info(account).2)
+					.unwrap_or_default();
+				let origin_tx_hash = transfer.origin_tx_hash().into();
+				relay::SerdeCall::Transfer(relay::SerdeTransfer {
+					relay_type: transfer.relay_type,
+					tx: transfer.tx.into(),
+					origin_tx: origin_tx.map(|x| Box::new(x.into())),
+					origin_tx_hash,
+					origin_shard,
+					origin_block_number: transfer.number.0,
+					origin_block_hash: transfer.hash.to_vec().into(),
+					origin_parent_hash: transfer.parent.to_vec().into(),
+					signature_valid: None,
+				})
+			}
+		}
+	}
+}
+
+impl From<sudo::Call> for sudo::SerdeCall {
+	fn from(t: sudo::Call) -> Self {
+		match t {
+			sudo::Call::Sudo(sudo) => sudo::SerdeCall::Sudo(sudo::SerdeSudo {
+				proposal: Box::new((*sudo.proposal).into()),
+			}),
+			sudo::Call::SetKey(set_key) => {
+				let (address, testnet_address, shard) = account_info(&set_key.new);
+				sudo::SerdeCall::SetKey(sudo::SerdeSetKey {
+					new: set_key.new,
+					new_address: address,
+					new_testnet_address: testnet_address,
+					new_shard: shard,
+				})
+			}
+		}
+	}
+}
+
+impl From<Era> for SerdeEra {
+	fn from(t: Era) -> Self {
+		match t {
+			Era::Immortal => Self::Immortal,
+			Era::Mortal(period, phase) => Self::Mortal(period, phase),
+		}
+	}
+}
+
+impl From<Transaction> for SerdeTransaction {
+	fn from(t: Transaction) -> Self {
+		let signature = t.signature.map(|(account, sig, nonce, era)| {
+			let (address, testnet_address, shard) = account_info(&account);
+
+			SerdeSignature {
+				sender: account.0.to_vec().into(),
+				sender_address: address,
+				sender_testnet_address: testnet_address,
+				sender_shard: shard,
+				signature: sig.to_vec().into(),
+				nonce: nonce.0,
+				era: era.into(),
+			}
+		});
+		Self {
+			signature,
+			call: t.call.into(),
+		}
+	}
+}
+
+fn get_search_filter(matches: &ArgMatches) -> Result<SearchFilter, String> {
+	let hash: Option<Vec<u8>> = match matches.value_of("HASH") {
+		Some(v) => Some(Hex::from_str(v)?.into()),
+		None => None,
+	};
+
+	let raw: Option<Vec<u8>> = match matches.value_of("RAW") {
+		Some(v) => Some(Hex::from_str(v)?.into()),
+		None => None,
+	};
+
+	let sender = match matches.value_of("SENDER") {
+		Some(v) => Some(parse_account(v)?),
+		None => None,
+	};
+
+	let dest = match matches.value_of("DEST") {
+		Some(v) => Some(parse_account(v)?),
+		None => None,
+	};
+
+	let call_names = match (matches.value_of("MODULE"), matches.value_of("METHOD")) {
+		(None, None) => None,
+		_ => Some(get_call_names(matches)?),
+	};
+
+	let module = match (matches.value_of("MODULE"), &call_names) {
+		(Some(v), Some(call_names)) => Some(call_names.parse_module(v)?),
+		_ => None,
+	};
+
+	let method = match (module, matches.value_of("METHOD"), &call_names) {
+		(Some(module), Some(v), Some(call_names)) => Some(call_names.parse_method(module, v)?),
+		_ => None,
+	};
+
+	let min_value = match matches.value_of("MIN_VALUE") {
+		Some(v) => Some(v.parse::<u128>().map_err(|_| "Invalid min value")?),
+		None => None,
+	};
+
+	let max_value = match matches.value_of("MAX_VALUE") {
+		Some(v) => Some(v.parse::<u128>().map_err(|_| "Invalid max value")?),
+		None => None,
+	};
+
+	let success = match (matches.is_present("SUCCESS"), matches.is_present("FAILED")) {
+		(true, _) => Some(true),
+		(_, true) => Some(false),
+		_ => None,
+	};
+
+	let nonce = match matches.value_of("NONCE") {
+		Some(v) => Some(v.parse::<u64>().map_err(|_| "Invalid nonce")?),
+		None => None,
+	};
+
+	let include_inherent = matches.is_present("INCLUDE_INHERENT");
+
+	Ok(SearchFilter {
+		hash,
+		raw,
+		sender,
+		dest,
+		module,
+		method,
+		min_value,
+		max_value,
+		success,
+		nonce,
+		include_inherent,
+	})
+}
+
+/// Parse account (0xff + public key) from address, public key hex or account hex
+fn parse_account(input: &str) -> Result<Vec<u8>, String> {
+	if let Ok(account) = input.parse::<Hex>() {
+		let account: Vec<u8> = account.into();
+		if account.len() == PUBLIC_KEY_LEN + 1 && account[0] == 0xff {
+			return Ok(account);
+		}
+	}
+	let public_key = parse_public_key(input)?;
+	let mut account = vec![0xffu8];
+	account.extend(public_key);
+	Ok(account)
+}
+
+/// Module names and their method names, in the order of index
+pub struct CallNames(Vec<(String, Vec<String>)>);
+
+impl CallNames {
+	/// From the runtime metadata, modules without a call type are skipped in the module index
+	pub fn from_metadata(rpc: &str) -> Result<Self, String> {
+		let metadata = get_metadata(rpc, None)?;
+		let modules = metadata
+			.modules
+			.into_iter()
+			.filter_map(|x| {
+				let calls = x.calls?;
+				Some((x.name, calls.into_iter().map(|x| x.name).collect()))
+			})
+			.collect();
+		Ok(CallNames(modules))
+	}
+
+	/// Built-in names as the offline fallback, which may be incomplete or out of date after runtime upgrade
+	pub fn offline() -> Self {
+		let modules = MODULE_NAMES
+			.iter()
+			.enumerate()
+			.map(|(module, &name)| {
+				let methods = method_names(module as u8)
+					.iter()
+					.map(|x| x.to_string())
+					.collect();
+				(name.to_string(), methods)
+			})
+			.collect();
+		CallNames(modules)
+	}
+
+	pub fn parse_module(&self, input: &str) -> Result<u8, String> {
+		match input.parse::<u8>() {
+			Ok(module) => Ok(module),
+			Err(_) => self
+				.0
+				.iter()
+				.position(|(name, _)| name == input)
+				.map(|x| x as u8)
+				.ok_or(format!("Unknown module: {}", input)),
+		}
+	}
+
+	pub fn parse_method(&self, module: u8, input: &str) -> Result<u8, String> {
+		match input.parse::<u8>() {
+			Ok(method) => Ok(method),
+			Err(_) => self
+				.0
+				.get(module as usize)
+				.and_then(|(_, methods)| methods.iter().position(|x| x == input))
+				.map(|x| x as u8)
+				.ok_or(format!("Unknown method: {}", input)),
+		}
+	}
+}
+
+/// Call names from the runtime metadata of the node (--rpc, --shard-rpc or the profile),
+/// or the built-in names if no rpc
+pub fn get_call_names(matches: &ArgMatches) -> Result<CallNames, String> {
+	match get_metadata_rpc(matches)? {
+		Some(rpc) => CallNames::from_metadata(&rpc),
+		None => Ok(CallNames::offline()),
 	}
 }
 
-impl From<call::balances::Transfer> for balances::Transfer {
-	fn from(t: call::balances::Transfer) -> Self {
-		let public = (t.dest.0)[1..].to_vec();
-		let address = public.to_address(Hrp::MAINNET).expect("qed").0;
-		let testnet_address = public.to_address(Hrp::TESTNET).expect("qed").0;
-		let shard = SHARD_COUNT_LIST
-			.iter()
-			.map(|&shard_count| {
-				let shard_num = shard_num_for_bytes(public.as_slice(), shard_count).expect("qed");
-				Shard {
-					shard_num,
-					shard_count,
-				}
-			})
-			.collect::<Vec<_>>();
-		balances::Transfer {
-			dest: t.dest,
-			value: t.value,
-			dest_address: address,
-			dest_testnet_address: testnet_address,
-			dest_shard: shard,
-		}
+/// RPC to get the runtime metadata: the rpc given, or any of the shard rpc
+fn get_metadata_rpc(matches: &ArgMatches) -> Result<Option<String>, String> {
+	match matches.value_of("RPC") {
+		Some(_) => Ok(Some(get_rpc(matches)?)),
+		None => Ok(base::get_shard_rpc_map(matches)?.values().next().cloned()),
 	}
 }
 
-impl From<Era> for SerdeEra {
-	fn from(t: Era) -> Self {
-		match t {
-			Era::Immortal => Self::Immortal,
-			Era::Mortal(period, phase) => Self::Mortal(period, phase),
-		}
+/// Module names in the order of module index, offline fallback of the runtime metadata
+const MODULE_NAMES: [&'static str; 12] = [
+	"timestamp",
+	"consensus",
+	"pow",
+	"indices",
+	"balances",
+	"sharding",
+	"crfg",
+	"finality_tracker",
+	"assets",
+	"relay",
+	"storage",
+	"sudo",
+];
+
+/// Method names in the order of method index, offline fallback of the runtime metadata
+fn method_names(module: u8) -> &'static [&'static str] {
+	match MODULE_NAMES.get(module as usize) {
+		Some(&"timestamp") => &["set"],
+		Some(&"consensus") => &[
+			"report_misbehavior",
+			"note_offline",
+			"remark",
+			"set_heap_pages",
+			"set_code",
+			"set_storage",
+			"kill_storage",
+		],
+		Some(&"balances") => &["transfer"],
+		Some(&"assets") => &["issue", "transfer"],
+		Some(&"relay") => &["transfer"],
+		Some(&"sudo") => &["sudo", "set_key"],
+		_ => &[],
 	}
 }
 
-impl From<Transaction> for SerdeTransaction {
-	fn from(t: Transaction) -> Self {
-		let signature = t.signature.map(|(account, sig, nonce, era)| {
-			let public = account.0[1..].to_vec();
-			let address = public.to_address(Hrp::MAINNET).expect("qed").0;
-			let testnet_address = public.to_address(Hrp::TESTNET).expect("qed").0;
-			let shard = SHARD_COUNT_LIST
-				.iter()
-				.map(|&shard_count| {
-					let shard_num =
-						shard_num_for_bytes(public.as_slice(), shard_count).expect("qed");
-					Shard {
-						shard_num,
-						shard_count,
-					}
-				})
-				.collect::<Vec<_>>();
+struct SearchFilter {
+	hash: Option<Vec<u8>>,
+	raw: Option<Vec<u8>>,
+	sender: Option<Vec<u8>>,
+	dest: Option<Vec<u8>>,
+	module: Option<u8>,
+	method: Option<u8>,
+	min_value: Option<u128>,
+	max_value: Option<u128>,
+	success: Option<bool>,
+	nonce: Option<u64>,
+	include_inherent: bool,
+}
 
-			SerdeSignature {
-				sender: account.0.to_vec().into(),
-				sender_address: address,
-				sender_testnet_address: testnet_address,
-				sender_shard: shard,
-				signature: sig.to_vec().into(),
-				nonce: nonce.0,
-				era: era.into(),
+impl SearchFilter {
+	fn accept(&self, item: &SearchItem) -> bool {
+		if let Some(hash) = &self.hash {
+			if hash != &item.hash {
+				return false;
 			}
-		});
-		Self {
-			signature,
-			call: t.call.into(),
 		}
-	}
-}
 
-fn accept_item(
-	item: &SearchItem,
-	expected_hash: Option<&Vec<u8>>,
-	expected_raw: Option<&Vec<u8>>,
-	expected_sender: Option<&Vec<u8>>,
-	include_inherent: bool,
-) -> bool {
-	if let Some(expected_hash) = expected_hash {
-		if expected_hash != &item.hash {
-			return false;
+		if let Some(raw) = &self.raw {
+			if raw != &item.raw {
+				return false;
+			}
+		}
+
+		if let Some(sender) = &self.sender {
+			let tx_sender = item
+				.tx
+				.signature
+				.as_ref()
+				.map(|(address, _, _, _)| address.0.to_vec());
+			if Some(sender) != tx_sender.as_ref() {
+				return false;
+			}
+		}
+
+		if let Some(nonce) = self.nonce {
+			let tx_nonce = item.tx.signature.as_ref().map(|(_, _, nonce, _)| nonce.0);
+			if Some(nonce) != tx_nonce {
+				return false;
+			}
+		}
+
+		if self.module.is_some() || self.method.is_some() {
+			let call = item.tx.call.encode();
+			if self.module.is_some() && self.module != call.get(0).cloned() {
+				return false;
+			}
+			if self.method.is_some() && self.method != call.get(1).cloned() {
+				return false;
+			}
+		}
+
+		if self.dest.is_some() || self.min_value.is_some() || self.max_value.is_some() {
+			let (dest, value) = match transfer_of(&item.tx) {
+				Some(transfer) => transfer,
+				None => return false,
+			};
+			if let Some(expected_dest) = &self.dest {
+				if expected_dest != &dest {
+					return false;
+				}
+			}
+			if let Some(min_value) = self.min_value {
+				if value < min_value {
+					return false;
+				}
+			}
+			if let Some(max_value) = self.max_value {
+				if value > max_value {
+					return false;
+				}
+			}
 		}
-	}
 
-	if let Some(expected_raw) = expected_raw {
-		if expected_raw != &item.raw {
+		if self.success.is_some() && self.success != item.success {
 			return false;
 		}
+
+		if !self.include_inherent {
+			let no_sig = item.tx.signature.is_none();
+			let is_relay_tx = match item.tx.call {
+				Call::Relay(_) => true,
+				_ => false,
+			};
+			if no_sig && !is_relay_tx {
+				return false;
+			}
+		}
+
+		true
 	}
+}
 
-	if let Some(expected_sender) = expected_sender {
-		let tx_sender = item
-			.tx
-			.signature
-			.as_ref()
-			.map(|(address, _, _, _)| address.0.to_vec());
-		if Some(expected_sender) != tx_sender.as_ref() {
-			return false;
+/// Get the dest account and value of a transfer, or of the origin transfer of a relay
+fn transfer_of(tx: &Transaction) -> Option<(Vec<u8>, u128)> {
+	match &tx.call {
+		Call::Balances(call::balances::Call::Transfer(transfer)) => {
+			Some((transfer.dest.0.to_vec(), transfer.value.0))
+		}
+		Call::Relay(_) => {
+			let origin_tx = relay_transfer(&tx.call)?.origin_tx()?;
+			match origin_tx.call {
+				Call::Relay(_) => None,
+				_ => transfer_of(&origin_tx),
+			}
 		}
+		_ => None,
 	}
+}
 
-	if !include_inherent {
-		let no_sig = item.tx.signature.is_none();
-		let is_relay_tx = match item.tx.call {
-			Call::Relay(_) => true,
-			_ => false,
-		};
-		if no_sig && !is_relay_tx {
-			return false;
+#[derive(Serialize)]
+pub struct AccountExtrinsic {
+	index: u32,
+	hash: Hex,
+	tx: SerdeTransaction,
+	success: Option<bool>,
+	events: Option<Vec<String>>,
+}
+
+/// Extrinsics in the block sent by the account, transferring to the account, or with events of the account
+pub fn get_account_extrinsics(
+	rpc: &str,
+	block_hash: &[u8],
+	public_key: &[u8],
+) -> Result<Vec<AccountExtrinsic>, String> {
+	let extrinsics = get_block_extrinsics(rpc, block_hash)?;
+	let results = get_block_extrinsics_result(rpc, block_hash)?;
+
+	filter_account_extrinsics(extrinsics, &results, public_key)
+}
+
+fn filter_account_extrinsics(
+	extrinsics: Vec<Vec<u8>>,
+	results: &HashMap<u32, (bool, Vec<String>)>,
+	public_key: &[u8],
+) -> Result<Vec<AccountExtrinsic>, String> {
+	// events are formatted by debug, in which accounts are in hex
+	let public_key_hex = hex::encode(public_key);
+
+	let mut result = vec![];
+	for (index, raw) in extrinsics.into_iter().enumerate() {
+		let tx: Transaction = Decode::decode(&mut &raw[..]).ok_or("invalid tx")?;
+		let extrinsic_result = results.get(&(index as u32));
+
+		let is_sender = tx
+			.signature
+			.as_ref()
+			.map(|(address, _, _, _)| &address.0[1..] == public_key)
+			.unwrap_or(false);
+		// dest is the account of 0xff and the public key, the same as the sender
+		let is_dest = transfer_of(&tx)
+			.map(|(dest, _)| &dest[1..] == public_key)
+			.unwrap_or(false);
+		let in_events = extrinsic_result
+			.map(|x| x.1.iter().any(|event| event.contains(&public_key_hex)))
+			.unwrap_or(false);
+
+		if is_sender || is_dest || in_events {
+			result.push(AccountExtrinsic {
+				index: index as u32,
+				hash: blake2_256(&raw).to_vec().into(),
+				tx: tx.into(),
+				success: extrinsic_result.map(|x| x.0),
+				events: extrinsic_result.map(|x| x.1.clone()),
+			});
 		}
 	}
 
-	true
+	Ok(result)
 }
 
 fn get_block_extrinsics(rpc: &str, block_hash: &[u8]) -> Result<Vec<Vec<u8>>, String> {
@@ -1085,6 +2734,63 @@ mod cases {
                     is_example: true,
                     is_test: true,
                     since: "0.1.0".to_string(),
+                }, Case {
+                    desc: "Verify tx signature".to_string(),
+                    input: vec!["verify", "--checkpoint-hash", "0x000004c65b2e9240dd85ddb101aef17d0cf2c2fdbe133ad9b44e870b445292d0", "0x290281ff46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a58f2dc97636eb02a98c4d16e2fab01e8516b305fd7575c1049782bb91fdaf20747c1a6bbdc8700e8db00526842d2ce9b12d53fc6ca1fbde71818ee520849200408d5020400ffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5ca10f"].into_iter().map(Into::into).collect(),
+                    output: vec![r#"{
+  "result": {
+    "sender_address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "nonce": 2,
+    "checkpoint_hash": "0x000004c65b2e9240dd85ddb101aef17d0cf2c2fdbe133ad9b44e870b445292d0",
+    "signature_valid": true,
+    "valid": true
+  }
+}"#].into_iter().map(Into::into).collect(),
+                    is_example: true,
+                    is_test: true,
+                    since: "0.14.0".to_string(),
+                }, Case {
+                    desc: "Verify tx signature of a changed tx".to_string(),
+                    input: vec!["verify", "--checkpoint-hash", "0x000004c65b2e9240dd85ddb101aef17d0cf2c2fdbe133ad9b44e870b445292d0", "0x290281ff46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a58f2dc97636eb02a98c4d16e2fab01e8516b305fd7575c1049782bb91fdaf20747c1a6bbdc8700e8db00526842d2ce9b12d53fc6ca1fbde71818ee520849200408d5020400ffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5ca50f"].into_iter().map(Into::into).collect(),
+                    output: vec![r#"{
+  "result": {
+    "sender_address": "yee1gm4ammuvmxa3vlwrppudwyfm0ctgumcxg6l0l4mad8fehttkk3aq0l3llv",
+    "nonce": 2,
+    "checkpoint_hash": "0x000004c65b2e9240dd85ddb101aef17d0cf2c2fdbe133ad9b44e870b445292d0",
+    "signature_valid": false,
+    "valid": false
+  }
+}"#].into_iter().map(Into::into).collect(),
+                    is_example: false,
+                    is_test: true,
+                    since: "0.14.0".to_string(),
+                }, Case {
+                    desc: "Verify tx signature and check against the node".to_string(),
+                    input: vec!["verify", "-r", "http://localhost:9033", "--number", "45", "0x290281ff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c706a16d3939a69e025592d997e68073a60008503d2d7251092b5e13e7b44f9367bf47c8f307624f10f348ca96a39cec64701c399518f82b43804e01cdf876c5c0708d5020400ffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5ca10f"].into_iter().map(Into::into).collect(),
+                    output: vec![r#"{
+  "result": {
+    "sender_address": "yee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqmtaf9v",
+    "nonce": 2,
+    "checkpoint_hash": "0x000004c65b2e9240dd85ddb101aef17d0cf2c2fdbe133ad9b44e870b445292d0",
+    "signature_valid": true,
+    "check": {
+      "era_valid": true,
+      "nonce_valid": true,
+      "account_nonce": 2,
+      "value_enough": true,
+      "free_balance": 99999000,
+      "shard_match": true,
+      "node_shard": {
+        "shard_num": 0,
+        "shard_count": 4
+      }
+    },
+    "valid": true
+  }
+}"#].into_iter().map(Into::into).collect(),
+                    is_example: true,
+                    is_test: false,
+                    since: "0.14.0".to_string(),
                 }, Case {
                     desc: "Compose tx".to_string(),
                     input: vec!["compose", "-r", "http://localhost:9033", "-k", "keystore.dat", "-c", r#"'{ "module":4, "method":0, "params":{"dest":"0xffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5c","value":1000}}'"#].into_iter().map(Into::into).collect(),
@@ -1105,6 +2811,93 @@ mod cases {
                     is_test: false,
                     since: "0.1.0".to_string(),
                 },
+                     Case {
+                    desc: "Compose tx by agent".to_string(),
+                    input: vec!["compose", "-r", "http://localhost:9033", "--agent", "/tmp/yee-agent.sock", "-c", r#"'{ "module":4, "method":0, "params":{"dest":"0xffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5c","value":1000}}'"#].into_iter().map(Into::into).collect(),
+                    output: vec![r#"{
+  "result": {
+    "shard_num": 0,
+    "shard_count": 4,
+    "sender_address": "yee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqmtaf9v",
+    "sender_testnet_address": "tyee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqkv6lyl",
+    "nonce": 2,
+    "period": 64,
+    "current": 45,
+    "current_hash": "0x000004c65b2e9240dd85ddb101aef17d0cf2c2fdbe133ad9b44e870b445292d0",
+    "raw": "0x290281ff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c706a16d3939a69e025592d997e68073a60008503d2d7251092b5e13e7b44f9367bf47c8f307624f10f348ca96a39cec64701c399518f82b43804e01cdf876c5c0708d5020400ffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5ca10f"
+  }
+}"#].into_iter().map(Into::into).collect(),
+                    is_example: true,
+                    is_test: false,
+                    since: "0.14.0".to_string(),
+                },
+                     Case {
+                         desc: "Preview sudo call".to_string(),
+                         input: vec!["sudo", "--preview", "-c", r#"'{ "module":"balances", "method":"transfer", "params":{"dest":"0xff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d","value":1000}}'"#].into_iter().map(Into::into).collect(),
+                         output: vec![r#"{
+  "result": {
+    "call": {
+      "Sudo": {
+        "Sudo": {
+          "proposal": {
+            "Balances": {
+              "Transfer": {
+                "dest": "0xff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d",
+                "dest_address": "yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn",
+                "dest_testnet_address": "tyee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7sa3q30q",
+                "dest_shard": [
+                  {
+                    "shard_num": 1,
+                    "shard_count": 4
+                  },
+                  {
+                    "shard_num": 5,
+                    "shard_count": 8
+                  }
+                ],
+                "value": 1000
+              }
+            }
+          }
+        }
+      }
+    },
+    "raw": "0x0b000400ff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3da10f"
+  }
+}"#].into_iter().map(Into::into).collect(),
+                         is_example: true,
+                         is_test: true,
+                         since: "0.14.0".to_string(),
+                     },
+                     Case {
+                         desc: "Compose sudo tx to kill storage".to_string(),
+                         input: vec!["sudo", "-r", "http://localhost:9033", "-k", "sudo.dat", "--kill-storage", "0x3a686561707061676573"].into_iter().map(Into::into).collect(),
+                         output: vec![r#"{
+  "result": {
+    "shard_num": 0,
+    "shard_count": 4,
+    "sender_address": "yee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqmtaf9v",
+    "sender_testnet_address": "tyee1jfakj2rvqym79lmxcmjkraep6tn296deyspd9mkh467u4xgqt3cqkv6lyl",
+    "nonce": 3,
+    "period": 64,
+    "current": 52,
+    "current_hash": "0x...",
+    "raw": "0x...",
+    "call": {
+      "Sudo": {
+        "Sudo": {
+          "proposal": {
+            "Consensus": "..."
+          }
+        }
+      }
+    }
+  }
+}"#].into_iter().map(Into::into).collect(),
+                         is_example: true,
+                         is_test: false,
+                         since: "0.14.0".to_string(),
+                     },
                      Case {
                          desc: "Submit tx".to_string(),
                          input: vec!["submit", "-r", "http://localhost:9033", "0x310281ff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70a669fea60899f954d36146355528c0a24f8e6a7d2d04fe78384e4c5f9e0b8231560fbb54b967e0c868f23c3f9d141641b064688b0683d56741af6908b9fbeb012045010400ff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d0284d717"].into_iter().map(Into::into).collect(),
@@ -1161,6 +2954,104 @@ mod cases {
                          is_example: true,
                          is_test: false,
                          since: "0.1.0".to_string(),
+                     },
+                     Case {
+                         desc: "Search transfer by dest and value".to_string(),
+                         input: vec!["search", "-r", "http://localhost:9033", "--from", "60", "--to", "70", "--dest", "yee1jnvc3dpdjmwt6es9larlr8r2kd0kym43hj9m6284nf6fj7398g7ssk88wn", "--min-value", "100000000", "--success"].into_iter().map(Into::into).collect(),
+                         output: vec![r#"{
+  "result": [
+    {
+      "hash": "0xad1eeb7f893dc1a7104d91caa0418b38ebe43880e5e79341bff50edc90aeb2bf",
+      "raw": "0x310281ff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70b4c7fd2f9484e881a8e57132412575b8978a7443ddb13b98052dfc62f2dbb35f72bc5c25e42b2be4b3125f9ab5362a1b3826df0744370e70788f8f3eb25c100c00e5030400ff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d0284d717",
+      "tx": {
+        "signature": {
+          "sender": "0xff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70",
+          "signature": "0xb4c7fd2f9484e881a8e57132412575b8978a7443ddb13b98052dfc62f2dbb35f72bc5c25e42b2be4b3125f9ab5362a1b3826df0744370e70788f8f3eb25c100c",
+          "nonce": 0,
+          "era": {
+            "Mortal": [
+              64,
+              62
+            ]
+          }
+        },
+        "call": {
+          "module": 4,
+          "method": 0,
+          "params": {
+            "dest": "0xff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d",
+            "value": 100000000
+          }
+        }
+      },
+      "block": {
+        "Number": {
+          "number": 63,
+          "hash": "0x453822219ba447ad31bc7c5499a6a09e475435f7bb9e43b885a8d38c06b50643",
+          "index": 5
+        }
+      },
+      "success": true,
+      "events": [
+        "balances(Transfer(927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70 (5FNmWUUd...), 94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d (5FRsZjZU...), 100000000, 0))"
+      ]
+    }
+  ]
+}"#].into_iter().map(Into::into).collect(),
+                         is_example: true,
+                         is_test: false,
+                         since: "0.14.0".to_string(),
+                     },
+                     Case {
+                         desc: "Track cross-shard transfer".to_string(),
+                         input: vec!["track", "--shard-rpc", "0=http://localhost:9033", "--shard-rpc", "1=http://localhost:19033", "--shard-rpc", "2=http://localhost:29033", "--shard-rpc", "3=http://localhost:39033", "0xad1eeb7f893dc1a7104d91caa0418b38ebe43880e5e79341bff50edc90aeb2bf"].into_iter().map(Into::into).collect(),
+                         output: vec![r#"{
+  "result": {
+    "hash": "0xad1eeb7f893dc1a7104d91caa0418b38ebe43880e5e79341bff50edc90aeb2bf",
+    "status": "Completed",
+    "cross_shard": true,
+    "origin": {
+      "shard": {
+        "shard_num": 0,
+        "shard_count": 4
+      },
+      "hash": "0xad1eeb7f893dc1a7104d91caa0418b38ebe43880e5e79341bff50edc90aeb2bf",
+      "block": {
+        "Number": {
+          "number": 63,
+          "hash": "0x453822219ba447ad31bc7c5499a6a09e475435f7bb9e43b885a8d38c06b50643",
+          "index": 5
+        }
+      },
+      "timestamp": 1590046800000,
+      "success": true,
+      "events": [
+        "balances(Transfer(927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70 (5FNmWUUd...), 94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d (5FRsZjZU...), 100000000, 0))"
+      ]
+    },
+    "destination": {
+      "shard": {
+        "shard_num": 1,
+        "shard_count": 4
+      },
+      "hash": "0x2c9a2e7e0f3d6f2b2f1b8a3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f70",
+      "block": {
+        "Number": {
+          "number": 58,
+          "hash": "0x8e0f1f5a5b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6",
+          "index": 2
+        }
+      },
+      "timestamp": 1590046860000,
+      "success": true,
+      "events": []
+    },
+    "latency": 60000
+  }
+}"#].into_iter().map(Into::into).collect(),
+                         is_example: true,
+                         is_test: false,
+                         since: "0.14.0".to_string(),
                      }],
             )
         ].into_iter().collect()
@@ -1177,4 +3068,68 @@ mod tests {
 	fn test_cases() {
 		test_module(module());
 	}
+
+	#[test]
+	fn test_era_births() {
+		assert_eq!(era_births(&Era::Immortal, 200), Ok(vec![0]));
+		assert_eq!(
+			era_births(&Era::Mortal(64, 45), 200),
+			Ok(vec![173, 109, 45])
+		);
+		assert_eq!(era_births(&Era::Mortal(64, 45), 45), Ok(vec![45]));
+		assert!(era_births(&Era::Mortal(64, 45), 44).is_err());
+		assert_eq!(
+			era_births(&Era::Mortal(4, 1), 100).map(|x| x.len()),
+			Ok(EXPIRED_ERAS as usize + 1)
+		);
+	}
+
+	#[test]
+	fn test_filter_account_extrinsics() {
+		// 0: 0x927b… transfers to 0xa615…, 1: 0x927b… transfers to 0x94d9…
+		let extrinsics: Vec<Vec<u8>> = vec![
+			"0x290281ff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70a8796f3650bf99d094f7004f27849bf712ce7a032425ce13b8e334ff834b084f3a7ead9eb04520912a1018c26d3c49519f6d70c7fa4f799fa33b007854efd40f00a5030400ffa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5ca10f",
+			"0x310281ff927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70b4c7fd2f9484e881a8e57132412575b8978a7443ddb13b98052dfc62f2dbb35f72bc5c25e42b2be4b3125f9ab5362a1b3826df0744370e70788f8f3eb25c100c00e5030400ff94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d0284d717",
+		]
+		.into_iter()
+		.map(|x| Hex::from_str(x).unwrap().into())
+		.collect();
+
+		let other = "0102030405060708091011121314151617181920212223242526272829303132";
+		let mut results = HashMap::new();
+		results.insert(0, (true, vec![]));
+		let event = format!("Indices(NewAccountIndex({}, 1))", other);
+		results.insert(1, (true, vec![event]));
+
+		let indices = |public_key: &str| {
+			let public_key: Vec<u8> = Hex::from_str(public_key).unwrap().into();
+			filter_account_extrinsics(extrinsics.clone(), &results, &public_key)
+				.unwrap()
+				.into_iter()
+				.map(|x| x.index)
+				.collect::<Vec<_>>()
+		};
+
+		// sender
+		assert_eq!(
+			indices("0x927b69286c0137e2ff66c6e561f721d2e6a2e9b92402d2eed7aebdca99005c70"),
+			vec![0, 1]
+		);
+		// receiver
+		assert_eq!(
+			indices("0xa6158c2b928d5d495922366ad9b4339a023366b322fb22f4db12751e0ea93f5c"),
+			vec![0]
+		);
+		assert_eq!(
+			indices("0x94d988b42d96dcbd6605ff47f19c6ab35f626eb1bc8bbd28f59a74997a253a3d"),
+			vec![1]
+		);
+		// in events
+		assert_eq!(indices(&format!("0x{}", other)), vec![1]);
+		// none
+		assert_eq!(
+			indices("0x0000000000000000000000000000000000000000000000000000000000000000"),
+			Vec::<u32>::new()
+		);
+	}
 }